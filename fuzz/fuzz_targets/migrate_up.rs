@@ -0,0 +1,25 @@
+//! Migrates arbitrary bytes to the latest format version.
+//!
+//! Invalid JSON should result in an error, never a panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use migrate_rustdoc_json::{
+    migrations,
+    output::OutputOptions,
+    reporter::{Reporter, Verbosity},
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(json) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = migrations::migrate_up(
+        json,
+        migrations::MAXIMUM_VERSION,
+        &OutputOptions::default(),
+        &mut Reporter::new(Verbosity::Quiet),
+    );
+});
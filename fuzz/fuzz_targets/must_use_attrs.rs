@@ -0,0 +1,39 @@
+//! Fuzzes the `#[must_use]` conversion used when migrating from v51 to v52.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use migrate_rustdoc_json::attributes::Attribute;
+use migrate_rustdoc_json_fuzz::migrate_attr;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(attr) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let migrated = migrate_attr(attr, 51, 52);
+
+    assert_eq!(
+        migrated.len(),
+        1,
+        "{attr:?} was migrated to {migrated:?}, but attributes should never be added or removed",
+    );
+
+    let migrated = &migrated[0];
+
+    // Only the form used by v51, whose string literal must be valid, is converted. Other
+    // attributes, including the form used since v52, must pass through unchanged.
+    match Attribute::parse(attr) {
+        attribute @ Attribute::MustUse { .. } if attr.starts_with("#[must_use") => {
+            assert_eq!(Some(migrated), attribute.render(52).as_ref());
+            assert!(
+                migrated.starts_with("#[attr = MustUse"),
+                "{attr:?} was migrated to {migrated:?}, which is not a `#[attr = MustUse ...]`",
+            );
+
+            // The message must survive being escaped with `Debug`.
+            assert_eq!(Attribute::parse(migrated), attribute);
+        }
+        _ => assert_eq!(migrated, attr),
+    }
+});
@@ -0,0 +1,33 @@
+//! Fuzzes the `#[attr = Repr(...)]` parser used when migrating from v42 to v43.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use migrate_rustdoc_json::attributes::Attribute;
+use migrate_rustdoc_json_fuzz::migrate_attr;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(attr) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let migrated = migrate_attr(attr, 42, 43);
+
+    // Only the `Debug` form used by v42 is converted. Other attributes, including the pretty form
+    // used since v43, must pass through unchanged.
+    let expected: Vec<String> = match Attribute::parse(attr) {
+        attribute @ Attribute::Repr(_) if attr.starts_with("#[attr = Repr(") => {
+            attribute.render(43).into_iter().collect()
+        }
+        _ => vec![attr.to_owned()],
+    };
+
+    assert_eq!(migrated, expected, "{attr:?} was migrated incorrectly");
+
+    if let [pretty] = migrated.as_slice() {
+        assert!(
+            pretty == attr || (pretty.starts_with("#[repr(") && pretty.ends_with(")]")),
+            "{attr:?} was migrated to {pretty:?}, which is not a `#[repr(...)]`",
+        );
+    }
+});
@@ -0,0 +1,43 @@
+//! Helpers shared between fuzz targets.
+
+// The fixtures of the integration tests only depend on `serde_json`, so they are shared here.
+#[path = "../../tests/harness/fixture.rs"]
+pub mod fixture;
+
+use migrate_rustdoc_json::{
+    migrations,
+    output::OutputOptions,
+    reporter::{Reporter, Verbosity},
+};
+use serde_json::{Value, json};
+
+/// Returns the JSON of a minimal `Crate` whose root module only has the attribute `attr`.
+pub fn crate_with_attr(format_version: u32, attr: &str) -> String {
+    let mut crate_ = fixture::minimal_crate(format_version, []);
+
+    crate_["index"]["0"]["attrs"] = json!([attr]);
+
+    crate_.to_string()
+}
+
+/// Migrates [`crate_with_attr()`] from `format_version` to `to_version`, returning the root
+/// module's migrated attributes.
+///
+/// # Panics
+///
+/// Panics if the migration fails, as a crate with a single attribute should always be migratable.
+pub fn migrate_attr(attr: &str, format_version: u32, to_version: u32) -> Vec<String> {
+    let json = crate_with_attr(format_version, attr);
+
+    let migrated = migrations::migrate_up(
+        &json,
+        to_version,
+        &OutputOptions::default(),
+        &mut Reporter::new(Verbosity::Quiet),
+    )
+    .unwrap_or_else(|error| panic!("failed to migrate attribute {attr:?}: {error:?}"));
+
+    let migrated: Value = serde_json::from_str(&migrated).unwrap();
+
+    serde_json::from_value(migrated["index"]["0"]["attrs"].clone()).unwrap()
+}
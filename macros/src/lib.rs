@@ -0,0 +1,76 @@
+//! Procedural macros used by `migrate_rustdoc_json`.
+//!
+//! These macros read the source code of the `rustdoc_types` dependencies in order to generate
+//! code for every type they define. They are only intended to be used within
+//! `migrate_rustdoc_json` itself.
+
+mod schema;
+mod shape;
+mod source;
+mod unchanged;
+
+use proc_macro::TokenStream;
+
+/// Implements `MigrateUp` for all `rustdoc_types` types that do not change in this version.
+///
+/// The macro takes the names of the current and newer `rustdoc_types` dependencies, and a list of
+/// types that have manual `MigrateUp` implementations. It then reads the source code of both
+/// dependencies, and generates a structural implementation for every type defined in the current
+/// version that is not listed in `manual`.
+///
+/// A type may be migrated structurally if:
+///
+/// - It is a struct, and its field names and types are identical in both versions.
+/// - It is an enum, and all of its variants exist with identical fields in the newer version. (New
+///   variants may be added.)
+///
+/// If a type does not meet these conditions, it has diverged and the macro will emit a compile
+/// error describing the change. Implement `MigrateUp` manually for that type, then add it to
+/// `manual`.
+///
+/// `Crate` is special-cased: instead of being migrated, its `format_version` field is set to the
+/// newer version's `FORMAT_VERSION`.
+///
+/// # Example
+///
+/// ```ignore
+/// use migrate_rustdoc_json_macros::impl_unchanged_migrations;
+///
+/// // `Item` has a manual `MigrateUp` implementation, so it is excluded.
+/// impl_unchanged_migrations! {
+///     current: rustdoc_types_42,
+///     up: rustdoc_types_43,
+///     manual: [Item],
+/// }
+/// ```
+#[proc_macro]
+pub fn impl_unchanged_migrations(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as unchanged::Input);
+
+    unchanged::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generates the JSON Schema of the Rustdoc JSON defined by a `rustdoc_types` dependency.
+///
+/// The macro takes the name of a `rustdoc_types` dependency, reads its source code, and expands to
+/// a string literal containing the schema. Every public type is defined in `$defs` under its name,
+/// and the schema itself refers to `Crate`. Structs are objects and enums are externally tagged, as
+/// they are (de)serialized by `serde`.
+///
+/// # Example
+///
+/// ```ignore
+/// use migrate_rustdoc_json_macros::json_schema;
+///
+/// const SCHEMA: &str = json_schema!(rustdoc_types_53);
+/// ```
+#[proc_macro]
+pub fn json_schema(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::Ident);
+
+    schema::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,546 @@
+//! The implementation of `json_schema!`.
+//!
+//! The schema mirrors how `serde` (de)serializes each type: structs are objects, enums are
+//! externally tagged, and `#[serde(...)]` attributes that rename or default fields are respected.
+//! `rustdoc_types` only uses a handful of `serde` attributes, so any attribute that would change the
+//! shape of the JSON in a way that is not supported here is reported as an error rather than
+//! ignored.
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{ToTokens, quote};
+use serde_json::{Map, Value, json};
+use syn::{Ident, ext::IdentExt, spanned::Spanned};
+
+use crate::source;
+
+/// The JSON Schema dialect of the generated schemas.
+const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+pub fn expand(extern_name: Ident) -> syn::Result<TokenStream> {
+    let source = source::read_source(&extern_name.to_string())
+        .map_err(|error| syn::Error::new(extern_name.span(), error))?;
+
+    let schema = schema(&source).map_err(|error| {
+        syn::Error::new(
+            extern_name.span(),
+            format!("failed to generate the JSON Schema of `{extern_name}`: {error}"),
+        )
+    })?;
+
+    let schema = Literal::string(&schema.to_string());
+
+    Ok(quote!(#schema))
+}
+
+/// Generates the JSON Schema of the `Crate` defined in a `rustdoc_types` source file.
+fn schema(source: &str) -> syn::Result<Value> {
+    let file = syn::parse_file(source)?;
+
+    let format_version = file
+        .items
+        .iter()
+        .find_map(|item| match item {
+            syn::Item::Const(item) if item.ident == "FORMAT_VERSION" => Some(item),
+            _ => None,
+        })
+        .ok_or_else(|| syn::Error::new(Span::call_site(), "could not find `FORMAT_VERSION`"))
+        .and_then(|item| match &*item.expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit),
+                ..
+            }) => lit.base10_parse::<u32>(),
+            expr => Err(syn::Error::new(
+                expr.span(),
+                "`FORMAT_VERSION` is not an integer literal",
+            )),
+        })?;
+
+    // The names of all types, so that references to them can be told apart from unknown types.
+    let names: Vec<String> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Struct(item) if is_public(&item.vis) => Some(item.ident.to_string()),
+            syn::Item::Enum(item) if is_public(&item.vis) => Some(item.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let mut defs = Map::new();
+
+    for item in &file.items {
+        let (ident, def) = match item {
+            syn::Item::Struct(item) if is_public(&item.vis) => {
+                (&item.ident, struct_schema(item, &names))
+            }
+            syn::Item::Enum(item) if is_public(&item.vis) => {
+                (&item.ident, enum_schema(item, &names))
+            }
+            _ => continue,
+        };
+
+        let mut def =
+            def.map_err(|error| syn::Error::new(error.span(), format!("in `{ident}`, {error}")))?;
+
+        let name = ident.to_string();
+
+        // Rustdoc always writes the format version it was built with.
+        if name == "Crate" {
+            def["properties"]["format_version"]["const"] = Value::from(format_version);
+        }
+
+        def["title"] = Value::from(name.as_str());
+
+        if let Some(docs) = docs(item_attrs(item)) {
+            def["description"] = Value::from(docs);
+        }
+
+        defs.insert(name, def);
+    }
+
+    Ok(json!({
+        "$schema": DIALECT,
+        "title": format!("Rustdoc JSON format version v{format_version}"),
+        "$ref": "#/$defs/Crate",
+        "$defs": defs,
+    }))
+}
+
+fn is_public(vis: &syn::Visibility) -> bool {
+    matches!(vis, syn::Visibility::Public(_))
+}
+
+fn item_attrs(item: &syn::Item) -> &[syn::Attribute] {
+    match item {
+        syn::Item::Struct(item) => &item.attrs,
+        syn::Item::Enum(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+fn struct_schema(item: &syn::ItemStruct, names: &[String]) -> syn::Result<Value> {
+    ensure_no_generics(&item.generics)?;
+
+    let attrs = ContainerAttrs::parse(&item.attrs)?;
+
+    match &item.fields {
+        // Newtype structs, such as `Id`, are (de)serialized as their only field.
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            type_schema(&fields.unnamed[0].ty, names)
+        }
+        fields if attrs.transparent => Err(syn::Error::new(
+            fields.span(),
+            "`#[serde(transparent)]` is only supported on newtype structs",
+        )),
+        fields => fields_schema(fields, &attrs, attrs.rename_all.as_deref(), names),
+    }
+}
+
+fn enum_schema(item: &syn::ItemEnum, names: &[String]) -> syn::Result<Value> {
+    ensure_no_generics(&item.generics)?;
+
+    let attrs = ContainerAttrs::parse(&item.attrs)?;
+
+    let mut variants = Vec::new();
+
+    for variant in &item.variants {
+        let variant_attrs = FieldAttrs::parse(&variant.attrs)?;
+
+        if variant_attrs.skip {
+            continue;
+        }
+
+        let name = match (variant_attrs.rename, &attrs.rename_all) {
+            (Some(rename), _) => rename,
+            (None, Some(rule)) => rename_variant(rule, &variant.ident.unraw().to_string())
+                .map_err(|error| syn::Error::new(variant.span(), error))?,
+            (None, None) => variant.ident.unraw().to_string(),
+        };
+
+        // Enums are externally tagged: unit variants are strings, and other variants are objects
+        // with a single key, the name of the variant.
+        let mut schema = match &variant.fields {
+            syn::Fields::Unit => json!({ "const": name }),
+            fields => {
+                let value = match fields {
+                    syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                        type_schema(&fields.unnamed[0].ty, names)?
+                    }
+                    fields => fields_schema(
+                        fields,
+                        &ContainerAttrs::default(),
+                        attrs.rename_all_fields.as_deref(),
+                        names,
+                    )?,
+                };
+
+                json!({
+                    "type": "object",
+                    "properties": { name.as_str(): value },
+                    "required": [&name],
+                    "additionalProperties": false,
+                })
+            }
+        };
+
+        if let Some(docs) = docs(&variant.attrs) {
+            schema["description"] = Value::from(docs);
+        }
+
+        variants.push(schema);
+    }
+
+    Ok(json!({ "oneOf": variants }))
+}
+
+/// Returns the schema of the fields of a struct or enum variant, other than a newtype.
+fn fields_schema(
+    fields: &syn::Fields,
+    attrs: &ContainerAttrs,
+    rename_all: Option<&str>,
+    names: &[String],
+) -> syn::Result<Value> {
+    match fields {
+        syn::Fields::Named(fields) => {
+            let mut properties = Map::new();
+            let mut required = Vec::new();
+
+            for field in &fields.named {
+                let field_attrs = FieldAttrs::parse(&field.attrs)?;
+
+                if field_attrs.skip {
+                    continue;
+                }
+
+                let ident = field.ident.as_ref().unwrap().unraw().to_string();
+
+                let name = match (field_attrs.rename, rename_all) {
+                    (Some(rename), _) => rename,
+                    (None, Some(rule)) => rename_field(rule, &ident)
+                        .map_err(|error| syn::Error::new(field.span(), error))?,
+                    (None, None) => ident,
+                };
+
+                let mut schema = type_schema(&field.ty, names)?;
+
+                if let Some(docs) = docs(&field.attrs) {
+                    schema["description"] = Value::from(docs);
+                }
+
+                // Missing `Option` fields are deserialized as `None`.
+                if !attrs.default && !field_attrs.default && !is_option(&field.ty) {
+                    required.push(Value::from(name.as_str()));
+                }
+
+                properties.insert(name, schema);
+            }
+
+            let mut schema = json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            });
+
+            if attrs.deny_unknown_fields {
+                schema["additionalProperties"] = Value::Bool(false);
+            }
+
+            Ok(schema)
+        }
+        syn::Fields::Unnamed(fields) => {
+            let items = fields
+                .unnamed
+                .iter()
+                .map(|field| type_schema(&field.ty, names))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            Ok(tuple_schema(items))
+        }
+        syn::Fields::Unit => Ok(json!({ "type": "null" })),
+    }
+}
+
+fn tuple_schema(items: Vec<Value>) -> Value {
+    let len = items.len();
+
+    json!({
+        "type": "array",
+        "prefixItems": items,
+        "items": false,
+        "minItems": len,
+    })
+}
+
+/// Returns the schema of a field's type.
+fn type_schema(ty: &syn::Type, names: &[String]) -> syn::Result<Value> {
+    let unsupported = || {
+        syn::Error::new(
+            ty.span(),
+            format!("unsupported type `{}`", ty.to_token_stream()),
+        )
+    };
+
+    let path = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        syn::Type::Tuple(tuple) => {
+            let items = tuple
+                .elems
+                .iter()
+                .map(|ty| type_schema(ty, names))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            return Ok(tuple_schema(items));
+        }
+        syn::Type::Paren(ty) => return type_schema(&ty.elem, names),
+        _ => return Err(unsupported()),
+    };
+
+    let segment = path.segments.last().ok_or_else(unsupported)?;
+    let name = segment.ident.to_string();
+
+    let args: Vec<&syn::Type> = match &segment.arguments {
+        syn::PathArguments::None => Vec::new(),
+        syn::PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .map(|arg| match arg {
+                syn::GenericArgument::Type(ty) => Ok(ty),
+                arg => Err(syn::Error::new(arg.span(), "unsupported generic argument")),
+            })
+            .collect::<syn::Result<_>>()?,
+        syn::PathArguments::Parenthesized(_) => return Err(unsupported()),
+    };
+
+    let schema = match (name.as_str(), args.as_slice()) {
+        ("Option", [ty]) => {
+            let some = type_schema(ty, names)?;
+
+            json!({ "anyOf": [some, { "type": "null" }] })
+        }
+        ("Box", [ty]) => type_schema(ty, names)?,
+        ("Vec", [ty]) => {
+            let items = type_schema(ty, names)?;
+
+            json!({ "type": "array", "items": items })
+        }
+        // Maps always have string keys in JSON, even if they are `Id`s.
+        ("HashMap" | "FxHashMap" | "BTreeMap", [_, value]) => {
+            let values = type_schema(value, names)?;
+
+            json!({ "type": "object", "additionalProperties": values })
+        }
+        ("String" | "PathBuf", []) => json!({ "type": "string" }),
+        ("bool", []) => json!({ "type": "boolean" }),
+        ("u8", []) => json!({ "type": "integer", "minimum": 0, "maximum": u8::MAX }),
+        ("u16", []) => json!({ "type": "integer", "minimum": 0, "maximum": u16::MAX }),
+        ("u32", []) => json!({ "type": "integer", "minimum": 0, "maximum": u32::MAX }),
+        ("u64" | "usize", []) => json!({ "type": "integer", "minimum": 0 }),
+        ("i8" | "i16" | "i32" | "i64" | "isize", []) => json!({ "type": "integer" }),
+        ("f32" | "f64", []) => json!({ "type": "number" }),
+        (name, []) if names.iter().any(|known| known == name) => {
+            json!({ "$ref": format!("#/$defs/{name}") })
+        }
+        _ => return Err(unsupported()),
+    };
+
+    Ok(schema)
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+fn ensure_no_generics(generics: &syn::Generics) -> syn::Result<()> {
+    if generics.params.is_empty() {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            generics.span(),
+            "generic types are not supported",
+        ))
+    }
+}
+
+/// Joins the doc comments of an item, field, or variant into a description.
+fn docs(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    }),
+                ..
+            }) => Some(lit.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_owned()
+        })
+        .collect();
+
+    let docs = lines.join("\n").trim().to_owned();
+
+    (!docs.is_empty()).then_some(docs)
+}
+
+/// The `#[serde(...)]` attributes of a struct or enum.
+#[derive(Default)]
+struct ContainerAttrs {
+    rename_all: Option<String>,
+    rename_all_fields: Option<String>,
+    default: bool,
+    deny_unknown_fields: bool,
+    transparent: bool,
+}
+
+impl ContainerAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+
+                if path.is_ident("rename_all") {
+                    parsed.rename_all = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if path.is_ident("rename_all_fields") {
+                    parsed.rename_all_fields = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if path.is_ident("default") {
+                    parsed.default = true;
+                } else if path.is_ident("deny_unknown_fields") {
+                    parsed.deny_unknown_fields = true;
+                } else if path.is_ident("transparent") {
+                    parsed.transparent = true;
+                } else if path.is_ident("rename") || path.is_ident("bound") {
+                    // These do not change the shape of the JSON.
+                    meta.value()?.parse::<syn::LitStr>()?;
+                } else {
+                    return Err(meta.error("unsupported `serde` attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// The `#[serde(...)]` attributes of a field or enum variant.
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<String>,
+    default: bool,
+    skip: bool,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+
+                if path.is_ident("rename") {
+                    parsed.rename = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if path.is_ident("default") {
+                    // `default = "path"` uses a custom default, which is still a default.
+                    if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<syn::LitStr>()?;
+                    }
+
+                    parsed.default = true;
+                } else if path.is_ident("skip") {
+                    parsed.skip = true;
+                } else if path.is_ident("skip_serializing_if") || path.is_ident("alias") {
+                    // Deserializing does not depend on these.
+                    meta.value()?.parse::<syn::LitStr>()?;
+                } else {
+                    return Err(meta.error("unsupported `serde` attribute"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Renames a `PascalCase` variant according to a `#[serde(rename_all = "...")]` rule.
+fn rename_variant(rule: &str, variant: &str) -> Result<String, String> {
+    let snake_case = || {
+        let mut snake = String::new();
+
+        for (i, c) in variant.char_indices() {
+            if i > 0 && c.is_uppercase() {
+                snake.push('_');
+            }
+
+            snake.push(c.to_ascii_lowercase());
+        }
+
+        snake
+    };
+
+    let renamed = match rule {
+        "lowercase" => variant.to_ascii_lowercase(),
+        "UPPERCASE" => variant.to_ascii_uppercase(),
+        "PascalCase" => variant.to_owned(),
+        "camelCase" => variant[..1].to_ascii_lowercase() + &variant[1..],
+        "snake_case" => snake_case(),
+        "SCREAMING_SNAKE_CASE" => snake_case().to_ascii_uppercase(),
+        "kebab-case" => snake_case().replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake_case().replace('_', "-").to_ascii_uppercase(),
+        rule => return Err(format!("unknown `rename_all` rule `{rule}`")),
+    };
+
+    Ok(renamed)
+}
+
+/// Renames a `snake_case` field according to a `#[serde(rename_all = "...")]` rule.
+fn rename_field(rule: &str, field: &str) -> Result<String, String> {
+    let pascal_case = || {
+        field
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect::<String>()
+    };
+
+    let renamed = match rule {
+        "lowercase" | "snake_case" => field.to_owned(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_ascii_uppercase(),
+        "PascalCase" => pascal_case(),
+        "camelCase" => {
+            let pascal = pascal_case();
+
+            pascal[..1].to_ascii_lowercase() + &pascal[1..]
+        }
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.replace('_', "-").to_ascii_uppercase(),
+        rule => return Err(format!("unknown `rename_all` rule `{rule}`")),
+    };
+
+    Ok(renamed)
+}
@@ -0,0 +1,162 @@
+//! Parsing and comparing the shapes of the types defined by `rustdoc_types`.
+//!
+//! A type's shape is its kind (struct or enum), its variant names, and its field names and types.
+//! Attributes and documentation are ignored, as they do not affect whether a type can be migrated
+//! structurally.
+
+use std::collections::BTreeMap;
+
+use quote::ToTokens;
+
+/// The shape of a single `struct` or `enum`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Struct(Fields),
+    Enum(Vec<Variant>),
+}
+
+/// A single variant of an `enum`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    pub name: String,
+    pub fields: Fields,
+}
+
+/// The fields of a `struct` or `enum` variant.
+///
+/// Field types are stored as strings with all whitespace removed, such as `Option<Box<Type>>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fields {
+    Named(Vec<(String, String)>),
+    Unnamed(Vec<String>),
+    Unit,
+}
+
+impl From<&syn::Fields> for Fields {
+    fn from(fields: &syn::Fields) -> Self {
+        match fields {
+            syn::Fields::Named(fields) => Self::Named(
+                fields
+                    .named
+                    .iter()
+                    .map(|field| {
+                        (
+                            field.ident.as_ref().unwrap().to_string(),
+                            type_to_string(&field.ty),
+                        )
+                    })
+                    .collect(),
+            ),
+            syn::Fields::Unnamed(fields) => Self::Unnamed(
+                fields
+                    .unnamed
+                    .iter()
+                    .map(|field| type_to_string(&field.ty))
+                    .collect(),
+            ),
+            syn::Fields::Unit => Self::Unit,
+        }
+    }
+}
+
+/// Parses the shapes of all public types defined in a `rustdoc_types` source file.
+///
+/// The returned map is keyed by the name of each type.
+pub fn parse_shapes(source: &str) -> syn::Result<BTreeMap<String, Shape>> {
+    let file = syn::parse_file(source)?;
+
+    let mut shapes = BTreeMap::new();
+
+    for item in file.items {
+        match item {
+            syn::Item::Struct(item) if matches!(item.vis, syn::Visibility::Public(_)) => {
+                shapes.insert(item.ident.to_string(), Shape::Struct((&item.fields).into()));
+            }
+            syn::Item::Enum(item) if matches!(item.vis, syn::Visibility::Public(_)) => {
+                let variants = item
+                    .variants
+                    .iter()
+                    .map(|variant| Variant {
+                        name: variant.ident.to_string(),
+                        fields: (&variant.fields).into(),
+                    })
+                    .collect();
+
+                shapes.insert(item.ident.to_string(), Shape::Enum(variants));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(shapes)
+}
+
+/// Checks whether a type with the `current` shape can be structurally migrated to the `up` shape.
+///
+/// Structs must have identical fields. Enums may gain new variants, but every existing variant must
+/// be unchanged. If the shapes are incompatible, a human-readable reason is returned.
+pub fn compare(current: &Shape, up: &Shape) -> Result<(), String> {
+    match (current, up) {
+        (Shape::Struct(current), Shape::Struct(up)) => compare_fields(current, up),
+        (Shape::Enum(current), Shape::Enum(up)) => {
+            for current_variant in current {
+                let Some(up_variant) = up
+                    .iter()
+                    .find(|variant| variant.name == current_variant.name)
+                else {
+                    return Err(format!("variant `{}` was removed", current_variant.name));
+                };
+
+                compare_fields(&current_variant.fields, &up_variant.fields)
+                    .map_err(|reason| format!("in variant `{}`, {reason}", current_variant.name))?;
+            }
+
+            Ok(())
+        }
+        (Shape::Struct(_), Shape::Enum(_)) => Err("it changed from a struct to an enum".to_owned()),
+        (Shape::Enum(_), Shape::Struct(_)) => Err("it changed from an enum to a struct".to_owned()),
+    }
+}
+
+fn compare_fields(current: &Fields, up: &Fields) -> Result<(), String> {
+    match (current, up) {
+        (Fields::Named(current), Fields::Named(up)) => {
+            for (name, current_type) in current {
+                match up.iter().find(|(up_name, _)| up_name == name) {
+                    Some((_, up_type)) if up_type == current_type => {}
+                    Some((_, up_type)) => {
+                        return Err(format!(
+                            "field `{name}` changed from `{current_type}` to `{up_type}`"
+                        ));
+                    }
+                    None => return Err(format!("field `{name}` was removed")),
+                }
+            }
+
+            if let Some((name, _)) = up
+                .iter()
+                .find(|(up_name, _)| !current.iter().any(|(name, _)| name == up_name))
+            {
+                return Err(format!("field `{name}` was added"));
+            }
+
+            Ok(())
+        }
+        (Fields::Unnamed(current), Fields::Unnamed(up)) if current == up => Ok(()),
+        (Fields::Unnamed(current), Fields::Unnamed(up)) => Err(format!(
+            "its fields changed from `({})` to `({})`",
+            current.join(", "),
+            up.join(", "),
+        )),
+        (Fields::Unit, Fields::Unit) => Ok(()),
+        _ => Err("the kind of its fields changed".to_owned()),
+    }
+}
+
+fn type_to_string(ty: &syn::Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
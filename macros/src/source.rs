@@ -0,0 +1,24 @@
+//! Reading the source code of the `rustdoc_types` dependencies.
+//!
+//! Proc macros cannot ask Cargo where a dependency's source code is, so the root source file of
+//! each `rustdoc_types` version is vendored in `sources/`, named after the dependency, such as
+//! `sources/rustdoc_types_53.rs`. `migrate_rustdoc_json` depends on the exact versions that are
+//! vendored, so the source always matches the types being compiled.
+
+use std::{fs, path::Path};
+
+/// Reads the vendored source code of the `rustdoc_types` dependency imported as `extern_name`.
+pub fn read_source(extern_name: &str) -> Result<String, String> {
+    // The vendored sources never change after they are added, so they are read when the macro is
+    // expanded rather than tracked by the compiler.
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("sources")
+        .join(format!("{extern_name}.rs"));
+
+    fs::read_to_string(&path).map_err(|error| {
+        format!(
+            "could not read the vendored source of `{extern_name}` at `{}`: {error}",
+            path.display(),
+        )
+    })
+}
@@ -0,0 +1,259 @@
+//! The implementation of `impl_unchanged_migrations!`.
+
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Ident, Token, bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+
+use crate::{
+    shape::{self, Fields, Shape},
+    source,
+};
+
+/// The parsed input of `impl_unchanged_migrations!`.
+pub struct Input {
+    current: Ident,
+    up: Ident,
+    manual: Vec<Ident>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let current = parse_key_value(input, "current")?;
+        input.parse::<Token![,]>()?;
+
+        let up = parse_key_value(input, "up")?;
+        input.parse::<Token![,]>()?;
+
+        parse_key(input, "manual")?;
+
+        let content;
+        bracketed!(content in input);
+        let manual = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+
+        // Allow a trailing comma.
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self {
+            current,
+            up,
+            manual,
+        })
+    }
+}
+
+/// Parses `key:`, erroring if the key does not match the expected name.
+fn parse_key(input: ParseStream, expected: &str) -> syn::Result<()> {
+    let key: Ident = input.parse()?;
+
+    if key != expected {
+        return Err(syn::Error::new(
+            key.span(),
+            format!("expected `{expected}`, found `{key}`"),
+        ));
+    }
+
+    input.parse::<Token![:]>()?;
+
+    Ok(())
+}
+
+/// Parses `key: value`, returning the value.
+fn parse_key_value(input: ParseStream, expected: &str) -> syn::Result<Ident> {
+    parse_key(input, expected)?;
+    input.parse()
+}
+
+pub fn expand(input: Input) -> syn::Result<TokenStream> {
+    let current_shapes = load_shapes(&input.current)?;
+    let up_shapes = load_shapes(&input.up)?;
+
+    let mut errors = Vec::new();
+
+    for manual in &input.manual {
+        if !current_shapes.contains_key(&manual.to_string()) {
+            errors.push(syn::Error::new(
+                manual.span(),
+                format!("`{manual}` does not exist in `{}`", input.current),
+            ));
+        }
+    }
+
+    let mut impls = TokenStream::new();
+
+    for (name, current_shape) in &current_shapes {
+        if input.manual.iter().any(|manual| manual == name) {
+            continue;
+        }
+
+        let diverged = match up_shapes.get(name) {
+            Some(up_shape) => shape::compare(current_shape, up_shape).err(),
+            None => Some("it was removed".to_owned()),
+        };
+
+        if let Some(reason) = diverged {
+            errors.push(syn::Error::new(
+                input.up.span(),
+                format!(
+                    "`{name}` changed between `{current}` and `{up}` because {reason}, so it needs a manual `MigrateUp` implementation listed in `manual: [...]`",
+                    current = input.current,
+                    up = input.up,
+                ),
+            ));
+
+            continue;
+        }
+
+        impls.extend(impl_migrate_up(&input, name, current_shape));
+    }
+
+    // Report all diverged types at once, rather than one per compilation.
+    if let Some(error) = errors.into_iter().reduce(|mut a, b| {
+        a.combine(b);
+        a
+    }) {
+        return Err(error);
+    }
+
+    Ok(impls)
+}
+
+fn load_shapes(extern_name: &Ident) -> syn::Result<BTreeMap<String, Shape>> {
+    let source = source::read_source(&extern_name.to_string())
+        .map_err(|error| syn::Error::new(extern_name.span(), error))?;
+
+    shape::parse_shapes(&source).map_err(|error| {
+        syn::Error::new(
+            extern_name.span(),
+            format!("failed to parse the source of `{extern_name}`: {error}"),
+        )
+    })
+}
+
+/// Generates the structural `MigrateUp` implementation for a single type.
+fn impl_migrate_up(input: &Input, name: &str, shape: &Shape) -> TokenStream {
+    let Input { current, up, .. } = input;
+    let ident = format_ident!("{}", name);
+
+    let body = match shape {
+        Shape::Struct(fields) => {
+            let (pattern, construct) = migrate_fields(
+                quote!(Self),
+                quote!(::#up::#ident),
+                fields,
+                // `Crate::format_version` is bumped instead of copied.
+                (name == "Crate").then(|| quote!(::#up::FORMAT_VERSION)),
+            );
+
+            quote! {
+                let #pattern = self;
+
+                #construct
+            }
+        }
+        Shape::Enum(variants) => {
+            let arms = variants.iter().map(|variant| {
+                let variant_ident = format_ident!("{}", variant.name);
+
+                let (pattern, construct) = migrate_fields(
+                    quote!(Self::#variant_ident),
+                    quote!(::#up::#ident::#variant_ident),
+                    &variant.fields,
+                    None,
+                );
+
+                quote!(#pattern => #construct,)
+            });
+
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+    };
+
+    // Every `Item` advances the progress of the migration.
+    let migrated_item = (name == "Item").then(|| quote!(reporter.migrated_item();));
+
+    quote! {
+        impl crate::traits::MigrateUp for ::#current::#ident {
+            type Up = ::#up::#ident;
+
+            fn migrate_up(
+                self,
+                #[allow(unused_variables)] reporter: &mut crate::reporter::Reporter,
+            ) -> Self::Up {
+                #migrated_item
+
+                #body
+            }
+        }
+    }
+}
+
+/// Returns the pattern that destructures a struct or variant, and the expression that constructs
+/// its migrated form.
+///
+/// If `format_version` is specified, the fields are those of `Crate`: the `format_version` field
+/// will be set to it instead of being migrated, and `index` and `paths` are migrated with
+/// `migrate_entries_up()`, which may migrate them in parallel.
+fn migrate_fields(
+    current_path: TokenStream,
+    up_path: TokenStream,
+    fields: &Fields,
+    format_version: Option<TokenStream>,
+) -> (TokenStream, TokenStream) {
+    match fields {
+        Fields::Named(fields) => {
+            let mut patterns = Vec::with_capacity(fields.len());
+            let mut values = Vec::with_capacity(fields.len());
+
+            for (name, _) in fields {
+                let field = format_ident!("{}", name);
+
+                match &format_version {
+                    Some(format_version) if name == "format_version" => {
+                        patterns.push(quote!(#field: _));
+                        values.push(quote!(#field: #format_version));
+                    }
+                    Some(_) if name == "index" || name == "paths" => {
+                        patterns.push(quote!(#field));
+                        values.push(
+                            quote!(#field: crate::primitives::migrate_entries_up(#field, reporter)),
+                        );
+                    }
+                    _ => {
+                        patterns.push(quote!(#field));
+                        values.push(
+                            quote!(#field: crate::traits::MigrateUp::migrate_up(#field, reporter)),
+                        );
+                    }
+                }
+            }
+
+            (
+                quote!(#current_path { #(#patterns),* }),
+                quote!(#up_path { #(#values),* }),
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<_> = (0..fields.len())
+                .map(|i| format_ident!("field_{}", i))
+                .collect();
+
+            (
+                quote!(#current_path(#(#bindings),*)),
+                quote!(#up_path(#(crate::traits::MigrateUp::migrate_up(#bindings, reporter)),*)),
+            )
+        }
+        Fields::Unit => (current_path, up_path),
+    }
+}
@@ -2,15 +2,82 @@
 
 use std::{convert::Infallible, env, ffi::OsStr, path::PathBuf};
 
-use anstream::println;
+use anstream::{ColorChoice, println};
 use anstyle::{AnsiColor, Color, Style};
 
-use crate::version::ToVersion;
+use crate::{filter::Filter, output::OutputOptions, reporter::Verbosity, version::ToVersion};
 
 #[derive(Debug)]
 pub struct Args {
+    pub color: ColorChoice,
+    pub verbosity: Verbosity,
+    pub command: Command,
+}
+
+/// The subcommand to run.
+#[derive(Debug)]
+pub enum Command {
+    /// Migrates Rustdoc JSON to a newer format version. This is the default when no subcommand is
+    /// given.
+    Migrate(MigrateArgs),
+    /// Prints the migration manifest of a migrated file.
+    Provenance(ProvenanceArgs),
+    /// Migrates several crates to a common format version and merges them into one workspace
+    /// document.
+    Merge(MergeArgs),
+    /// Prints the JSON Schema of a format version.
+    Schema(SchemaArgs),
+    /// Validates Rustdoc JSON against the JSON Schema of its format version.
+    Validate(ValidateArgs),
+    /// Compares the JSON Schemas of two format versions.
+    SchemaDiff(SchemaDiffArgs),
+}
+
+#[derive(Debug)]
+pub struct MigrateArgs {
     pub input: PathBuf,
+    pub output: Option<PathBuf>,
+    /// The format version of `input`, overriding its `format_version` field.
+    pub from_version: Option<u32>,
+    /// Whether to drop items and `paths` entries that cannot be deserialized, instead of failing.
+    pub lenient: bool,
     pub to_version: ToVersion,
+    pub output_options: OutputOptions,
+    /// Whether to write a migration manifest next to `output`.
+    pub manifest: bool,
+}
+
+#[derive(Debug)]
+pub struct MergeArgs {
+    pub inputs: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub to_version: ToVersion,
+    pub output_options: OutputOptions,
+}
+
+#[derive(Debug)]
+pub struct ProvenanceArgs {
+    /// The migrated JSON, or its manifest.
+    pub file: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct SchemaArgs {
+    /// The format version whose schema is printed.
+    pub version: ToVersion,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct ValidateArgs {
+    /// The Rustdoc JSON to validate.
+    pub file: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct SchemaDiffArgs {
+    pub from: ToVersion,
+    pub to: ToVersion,
 }
 
 pub fn parse_args() -> anyhow::Result<Args> {
@@ -28,16 +95,57 @@ pub fn parse_args() -> anyhow::Result<Args> {
         std::process::exit(0);
     }
 
-    if pico_args.contains(["-V", "--version"]) {
+    // Subcommands are optional, so that `--input` and `--to-version` may be passed directly.
+    let subcommand = pico_args.subcommand()?;
+
+    // `schema --version <VERSION>` takes a value, so it is not the `--version` flag.
+    if subcommand.as_deref() != Some("schema") && pico_args.contains(["-V", "--version"]) {
         print_version();
         std::process::exit(0);
     }
 
+    let verbosity = match (
+        pico_args.contains(["-q", "--quiet"]),
+        pico_args.contains(["-v", "--verbose"]),
+    ) {
+        (false, false) => Verbosity::Normal,
+        (true, false) => Verbosity::Quiet,
+        (false, true) => Verbosity::Verbose,
+        (true, true) => anyhow::bail!("`--quiet` and `--verbose` cannot be used together"),
+    };
+
+    let color = pico_args
+        .opt_value_from_fn("--color", parse_color)?
+        .unwrap_or(ColorChoice::Auto);
+
+    let command = match subcommand.as_deref() {
+        None | Some("migrate") => Command::Migrate(parse_migrate_args(&mut pico_args)?),
+        Some("provenance") => Command::Provenance(ProvenanceArgs {
+            file: pico_args
+                .free_from_os_str(|s| -> Result<PathBuf, Infallible> { Ok(s.into()) })?,
+        }),
+        Some("merge") => Command::Merge(parse_merge_args(&mut pico_args)?),
+        Some("schema") => Command::Schema(SchemaArgs {
+            version: pico_args.value_from_str("--version")?,
+            output: parse_output(&mut pico_args)?,
+        }),
+        Some("validate") => Command::Validate(ValidateArgs {
+            file: pico_args
+                .free_from_os_str(|s| -> Result<PathBuf, Infallible> { Ok(s.into()) })?,
+        }),
+        Some("schema-diff") => Command::SchemaDiff(SchemaDiffArgs {
+            from: pico_args.free_from_str()?,
+            to: pico_args.free_from_str()?,
+        }),
+        Some(subcommand) => anyhow::bail!(
+            "unknown subcommand `{subcommand}`, run with `--help` for a list of subcommands"
+        ),
+    };
+
     let args = Args {
-        input: pico_args.value_from_os_str("--input", |s| -> Result<PathBuf, Infallible> {
-            Ok(s.into())
-        })?,
-        to_version: pico_args.value_from_str("--to-version")?,
+        color,
+        verbosity,
+        command,
     };
 
     let remaining = pico_args.finish();
@@ -51,6 +159,89 @@ pub fn parse_args() -> anyhow::Result<Args> {
     Ok(args)
 }
 
+fn parse_migrate_args(pico_args: &mut pico_args::Arguments) -> anyhow::Result<MigrateArgs> {
+    let args = MigrateArgs {
+        input: pico_args.value_from_os_str("--input", |s| -> Result<PathBuf, Infallible> {
+            Ok(s.into())
+        })?,
+        output: parse_output(pico_args)?,
+        from_version: pico_args.opt_value_from_str("--from-version")?,
+        lenient: pico_args.contains("--lenient"),
+        to_version: pico_args.value_from_str("--to-version")?,
+        output_options: OutputOptions {
+            filter: Filter {
+                strip_private: pico_args.contains("--strip-private"),
+                strip_external_paths: pico_args.contains("--strip-external-paths"),
+                only_module: pico_args.opt_value_from_str("--only-module")?,
+            },
+            ..parse_output_options(pico_args)?
+        },
+        manifest: pico_args.contains("--manifest"),
+    };
+
+    anyhow::ensure!(
+        !args.manifest || args.output.is_some(),
+        "`--manifest` requires `--output`, as the manifest is written next to the output file"
+    );
+
+    Ok(args)
+}
+
+fn parse_merge_args(pico_args: &mut pico_args::Arguments) -> anyhow::Result<MergeArgs> {
+    let mut args = MergeArgs {
+        inputs: Vec::new(),
+        output: parse_output(pico_args)?,
+        to_version: pico_args.value_from_str("--to-version")?,
+        output_options: parse_output_options(pico_args)?,
+    };
+
+    // The inputs are the remaining free arguments, so all options must be parsed first.
+    while let Some(input) =
+        pico_args.opt_free_from_os_str(|s| -> Result<PathBuf, Infallible> { Ok(s.into()) })?
+    {
+        args.inputs.push(input);
+    }
+
+    anyhow::ensure!(
+        !args.inputs.is_empty(),
+        "`merge` requires at least one input file"
+    );
+
+    Ok(args)
+}
+
+fn parse_output(pico_args: &mut pico_args::Arguments) -> anyhow::Result<Option<PathBuf>> {
+    Ok(
+        pico_args.opt_value_from_os_str("--output", |s| -> Result<PathBuf, Infallible> {
+            Ok(s.into())
+        })?,
+    )
+}
+
+/// Parses the options that control how the output JSON is formatted.
+fn parse_output_options(pico_args: &mut pico_args::Arguments) -> anyhow::Result<OutputOptions> {
+    let pretty = pico_args.contains("--pretty");
+    let indent: Option<usize> = pico_args.opt_value_from_str("--indent")?;
+
+    Ok(OutputOptions {
+        // `--indent` implies `--pretty`.
+        indent: indent.or(pretty.then_some(OutputOptions::DEFAULT_INDENT)),
+        sort_keys: pico_args.contains("--sort-keys"),
+        ..Default::default()
+    })
+}
+
+fn parse_color(s: &str) -> anyhow::Result<ColorChoice> {
+    match s {
+        "auto" => Ok(ColorChoice::Auto),
+        "always" => Ok(ColorChoice::Always),
+        "never" => Ok(ColorChoice::Never),
+        _ => Err(anyhow::anyhow!(
+            "invalid `--color` value `{s}`, expected `auto`, `always`, or `never`"
+        )),
+    }
+}
+
 fn print_version() {
     const NAME: &str = env!("CARGO_PKG_NAME");
     const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -59,19 +250,55 @@ fn print_version() {
 }
 
 fn print_help() {
-    const BIN_NAME: &str = env!("CARGO_BIN_NAME");
+    // `CARGO_BIN_NAME` is not available to the library, but the binary shares the package name.
+    const BIN_NAME: &str = env!("CARGO_PKG_NAME");
 
     println!(
         "\
 Migrate Rustdoc's JSON output to newer format versions
 
-{bold_blue}Usage:{bold_blue:#} {blue}{BIN_NAME} --input <FILE> --to-version <VERSION> [OPTIONS]{blue:#}
+{bold_blue}Usage:{bold_blue:#} {blue}{BIN_NAME} [migrate] --input <FILE> --to-version <VERSION> [OPTIONS]{blue:#}
+       {blue}{BIN_NAME} merge --to-version <VERSION> [OPTIONS] <FILES>...{blue:#}
+       {blue}{BIN_NAME} provenance <FILE>{blue:#}
+       {blue}{BIN_NAME} schema --version <VERSION> [--output <FILE>]{blue:#}
+       {blue}{BIN_NAME} validate <FILE>{blue:#}
+       {blue}{BIN_NAME} schema-diff <VERSION> <VERSION>{blue:#}
+
+{bold_blue}Commands:{bold_blue:#}
+  {blue}migrate                       {blue:#}Migrates Rustdoc JSON to a newer format version (default)
+  {blue}merge <FILES>...              {blue:#}Migrates several crates to VERSION and merges them into one
+                                workspace document, resolving references between them
+  {blue}provenance <FILE>             {blue:#}Prints the migration manifest of a migrated file, or of the
+                                manifest FILE itself
+  {blue}schema                        {blue:#}Prints the JSON Schema of format version VERSION, generated from
+                                the `rustdoc_types` it was built with
+  {blue}validate <FILE>               {blue:#}Validates FILE against the JSON Schema of its format version,
+                                printing the path to every invalid value
+  {blue}schema-diff <A> <B>           {blue:#}Prints the types, fields, and variants that were added, removed,
+                                or changed from format version A to B
 
 {bold_blue}Options:{bold_blue:#}
-  {blue}-h, --help                  {blue:#}Prints the help text and exits
-  {blue}-V, --version               {blue:#}Prints the version info and exits
-  {blue}    --input <FILE>          {blue:#}The Rustdoc JSON to read
-  {blue}    --to-version <VERSION>  {blue:#}The format version to migrate to",
+  {blue}-h, --help                    {blue:#}Prints the help text and exits
+  {blue}-V, --version                 {blue:#}Prints the version info and exits
+  {blue}-q, --quiet                   {blue:#}Only prints errors
+  {blue}-v, --verbose                 {blue:#}Prints timing, item counts, and manual migrations for each version
+  {blue}    --color <WHEN>            {blue:#}When to use colors: `auto`, `always`, or `never` [default: auto]
+  {blue}    --input <FILE>            {blue:#}The Rustdoc JSON to read, which may be compressed
+  {blue}    --output <FILE>           {blue:#}The file to write the migrated JSON to, instead of stdout
+  {blue}    --from-version <VERSION>  {blue:#}The format version of `--input`, instead of detecting it
+  {blue}    --to-version <VERSION>    {blue:#}The format version to migrate to
+  {blue}    --lenient                 {blue:#}Drops items that cannot be read as the input's format version,
+                                instead of failing
+  {blue}    --pretty                  {blue:#}Pretty-prints the migrated JSON
+  {blue}    --indent <N>              {blue:#}Pretty-prints the migrated JSON with N spaces of indentation
+  {blue}    --sort-keys               {blue:#}Sorts object keys, ordering `index` and `paths` by ID
+  {blue}    --strip-private           {blue:#}Removes items that are not public, and their children
+  {blue}    --strip-external-paths    {blue:#}Removes `paths` entries from other crates that are not referenced
+  {blue}    --only-module <PATH>      {blue:#}Only keeps the items in the module PATH, such as `my_crate::foo`
+  {blue}    --manifest                {blue:#}Writes a migration manifest to `<OUTPUT>.migration.json`
+
+Files ending in `.gz` or `.zst` are compressed with Gzip or Zstandard. Compressed `--input` files
+are also detected by their contents.",
         bold_blue = Style::new().bold().fg_color(Some(Color::Ansi(AnsiColor::Blue))),
         blue = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Blue))),
     );
@@ -0,0 +1,609 @@
+//! A typed model of the attributes in `Item::attrs`, shared by every format version.
+//!
+//! Rustdoc has been gradually replacing the pretty-printed form of attributes with the form of
+//! their parsed `AttributeKind`, such as `#[attr = Inline(Hint)]`. [`Attribute::parse()`] accepts
+//! every form any format version has used, and [`Attribute::render()`] converts the attribute back
+//! to the form of a specific format version, so the same model is used to migrate attributes in
+//! either direction.
+//!
+//! |Attribute|Before|After|Since|
+//! |-|-|-|-|
+//! |`#[repr(...)]`|`#[attr = Repr([ReprC, ReprAlign(Align(8 bytes))])]\n`|`#[repr(C, align(8))]`|v43|
+//! |`#[inline]`|`#[inline(always)]`|`#[attr = Inline(Always)]`|v48|
+//! |`#[optimize]`|`#[optimize(none)]`|`#[attr = Optimize(DoNotOptimize)]`|v49|
+//! |`#[cold]`|`#[cold]`|`#[attr = Cold]`|v50|
+//! |`#[must_use]`|`#[must_use = "message"]`|`#[attr = MustUse {reason: "message"}]`|v52|
+//!
+//! The grammar of the [`Debug`] form of `#[repr(...)]` is documented in `migrations/v42.rs`.
+
+use std::fmt;
+
+use winnow::{
+    Parser,
+    ascii::{alpha1, alphanumeric1, digit1, space0},
+    combinator::{alt, delimited, dispatch, empty, fail, separated},
+    error::Result,
+};
+
+use crate::reporter::Reporter;
+
+/// An attribute from `Item::attrs`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    /// `#[repr(...)]`, with each of its arguments.
+    Repr(Vec<Repr>),
+    /// `#[inline]`, `#[inline(always)]`, or `#[inline(never)]`.
+    Inline(Inline),
+    /// `#[optimize(...)]`.
+    Optimize(Optimize),
+    /// `#[cold]`.
+    Cold,
+    /// `#[must_use]`, with its message if it has one.
+    ///
+    /// The message is unescaped, so it contains the actual characters of the message rather than
+    /// the contents of the string literal it was written as.
+    MustUse { reason: Option<String> },
+    /// Any other attribute, which is the same in every format version.
+    Other(String),
+}
+
+/// An argument of `#[repr(...)]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Repr {
+    /// `#[repr(i8)]`, `#[repr(usize)]`, and other integer types.
+    Int(IntType),
+    /// `#[repr(Rust)]`.
+    Rust,
+    /// `#[repr(C)]`.
+    C,
+    /// `#[repr(packed(N))]`, with the alignment in bytes.
+    Packed(u64),
+    /// `#[repr(simd)]`.
+    Simd,
+    /// `#[repr(transparent)]`.
+    Transparent,
+    /// `#[repr(align(N))]`, with the alignment in bytes.
+    Align(u64),
+}
+
+/// The integer type of `#[repr(...)]`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntType {
+    Signed(IntSize),
+    Unsigned(IntSize),
+}
+
+/// The size of an integer type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntSize {
+    /// `isize` or `usize`.
+    Size,
+    X8,
+    X16,
+    X32,
+    X64,
+    X128,
+}
+
+/// The kind of `#[inline]` attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Inline {
+    /// `#[inline]`.
+    Hint,
+    /// `#[inline(always)]`.
+    Always,
+    /// `#[inline(never)]`.
+    Never,
+}
+
+/// The kind of `#[optimize(...)]` attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Optimize {
+    /// `#[optimize(none)]`.
+    DoNotOptimize,
+    /// `#[optimize(speed)]`.
+    Speed,
+    /// `#[optimize(size)]`.
+    Size,
+}
+
+impl Attribute {
+    /// Parses an attribute in the form used by any format version.
+    ///
+    /// Attributes that are not recognized are returned as [`Attribute::Other`].
+    pub fn parse(attr: &str) -> Self {
+        let simple = match attr {
+            "#[inline]" | "#[attr = Inline(Hint)]" => Some(Self::Inline(Inline::Hint)),
+            "#[inline(always)]" | "#[attr = Inline(Always)]" => Some(Self::Inline(Inline::Always)),
+            "#[inline(never)]" | "#[attr = Inline(Never)]" => Some(Self::Inline(Inline::Never)),
+            "#[optimize(none)]" | "#[attr = Optimize(DoNotOptimize)]" => {
+                Some(Self::Optimize(Optimize::DoNotOptimize))
+            }
+            "#[optimize(speed)]" | "#[attr = Optimize(Speed)]" => {
+                Some(Self::Optimize(Optimize::Speed))
+            }
+            "#[optimize(size)]" | "#[attr = Optimize(Size)]" => {
+                Some(Self::Optimize(Optimize::Size))
+            }
+            "#[cold]" | "#[attr = Cold]" => Some(Self::Cold),
+            "#[must_use]" | "#[attr = MustUse]" => Some(Self::MustUse { reason: None }),
+            _ => None,
+        };
+
+        if let Some(attribute) = simple {
+            return attribute;
+        }
+
+        // The message is everything between the prefix and suffix, so that a message containing
+        // the suffix, such as `"]`, is still parsed as a single string literal.
+        if let Some(reason) = attr
+            .strip_prefix("#[must_use = ")
+            .and_then(|attr| attr.strip_suffix(']'))
+            .or_else(|| {
+                attr.strip_prefix("#[attr = MustUse {reason: ")
+                    .and_then(|attr| attr.strip_suffix("}]"))
+            })
+            .and_then(parse_string_literal)
+        {
+            return Self::MustUse {
+                reason: Some(reason),
+            };
+        }
+
+        match alt((parse_debug_reprs, parse_pretty_reprs)).parse(attr) {
+            Ok(reprs) => Self::Repr(reprs),
+            Err(_) => Self::Other(attr.to_owned()),
+        }
+    }
+
+    /// Returns the format version the form of this attribute last changed in, or `None` for
+    /// [`Attribute::Other`].
+    fn changed_in(&self) -> Option<u32> {
+        match self {
+            Self::Repr(_) => Some(43),
+            Self::Inline(_) => Some(48),
+            Self::Optimize(_) => Some(49),
+            Self::Cold => Some(50),
+            Self::MustUse { .. } => Some(52),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Renders this attribute in the form used by `format_version`.
+    ///
+    /// Returns `None` if the attribute is not included in that format version, such as
+    /// `#[repr(Rust)]` since v43.
+    pub fn render(&self, format_version: u32) -> Option<String> {
+        let attr = match self {
+            Self::Repr(reprs) if format_version < 43 => {
+                if reprs.is_empty() {
+                    return None;
+                }
+
+                let reprs: Vec<String> = reprs.iter().map(|repr| repr.debug()).collect();
+
+                format!("#[attr = Repr([{}])]\n", reprs.join(", "))
+            }
+            Self::Repr(reprs) => {
+                // `#[repr(Rust)]` is the default, so it is not included since v43.
+                let reprs: Vec<String> = reprs
+                    .iter()
+                    .filter(|repr| **repr != Repr::Rust)
+                    .map(ToString::to_string)
+                    .collect();
+
+                if reprs.is_empty() {
+                    return None;
+                }
+
+                format!("#[repr({})]", reprs.join(", "))
+            }
+            Self::Inline(inline) if format_version < 48 => match inline {
+                Inline::Hint => "#[inline]".to_owned(),
+                Inline::Always => "#[inline(always)]".to_owned(),
+                Inline::Never => "#[inline(never)]".to_owned(),
+            },
+            Self::Inline(inline) => {
+                let kind = match inline {
+                    Inline::Hint => "Hint",
+                    Inline::Always => "Always",
+                    Inline::Never => "Never",
+                };
+
+                format!("#[attr = Inline({kind})]")
+            }
+            Self::Optimize(optimize) if format_version < 49 => {
+                let kind = match optimize {
+                    Optimize::DoNotOptimize => "none",
+                    Optimize::Speed => "speed",
+                    Optimize::Size => "size",
+                };
+
+                format!("#[optimize({kind})]")
+            }
+            Self::Optimize(optimize) => {
+                let kind = match optimize {
+                    Optimize::DoNotOptimize => "DoNotOptimize",
+                    Optimize::Speed => "Speed",
+                    Optimize::Size => "Size",
+                };
+
+                format!("#[attr = Optimize({kind})]")
+            }
+            Self::Cold if format_version < 50 => "#[cold]".to_owned(),
+            Self::Cold => "#[attr = Cold]".to_owned(),
+            // Before v52, Rustdoc printed the string literal as it was written in the source code,
+            // which cannot be recovered. `Debug` produces an equivalent string literal instead.
+            Self::MustUse { reason } if format_version < 52 => match reason {
+                Some(reason) => format!("#[must_use = {reason:?}]"),
+                None => "#[must_use]".to_owned(),
+            },
+            // Since v52, Rustdoc prints the message with `Debug`.
+            Self::MustUse { reason } => match reason {
+                Some(reason) => format!("#[attr = MustUse {{reason: {reason:?}}}]"),
+                None => "#[attr = MustUse]".to_owned(),
+            },
+            Self::Other(attr) => attr.clone(),
+        };
+
+        Some(attr)
+    }
+}
+
+/// Converts an attribute to the form used by `to_version`.
+///
+/// Returns `None` if the attribute is not included in that format version. Unrecognized attributes
+/// are returned unchanged.
+pub(crate) fn migrate(attr: String, to_version: u32) -> Option<String> {
+    match Attribute::parse(&attr) {
+        Attribute::Other(_) => Some(attr),
+        attribute => attribute.render(to_version),
+    }
+}
+
+/// The families of attributes whose form changed, with the format version they changed in and the
+/// prefix shared by their old forms.
+const CHANGED_FAMILIES: &[(u32, &str)] = &[
+    (43, "#[attr = Repr("),
+    (48, "#[inline"),
+    (49, "#[optimize"),
+    (50, "#[cold"),
+    (52, "#[must_use"),
+];
+
+/// Converts the attributes of the item `item` from `from_version` to the form used by `to_version`.
+///
+/// Only attributes in the old form of a family that changed between the two format versions are
+/// converted, so that the rest keep the exact text Rustdoc emitted. Attributes that look like they belong to such a family,
+/// but could not be parsed, are left unchanged and reported as caveats of the item.
+// Only the migrations that change attributes use this, which may all be disabled.
+#[cfg_attr(
+    not(any(
+        all(feature = "v42", feature = "v43"),
+        all(feature = "v46", feature = "v48"),
+        all(feature = "v48", feature = "v49"),
+        all(feature = "v49", feature = "v50"),
+        all(feature = "v51", feature = "v52"),
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn migrate_attrs(
+    attrs: Vec<String>,
+    item: u32,
+    from_version: u32,
+    to_version: u32,
+    reporter: &mut Reporter,
+) -> Vec<String> {
+    let changed = |changed_in: u32| from_version < changed_in && changed_in <= to_version;
+
+    attrs
+        .into_iter()
+        .filter_map(|attr| {
+            let attribute = Attribute::parse(&attr);
+
+            if let Attribute::Other(_) = attribute {
+                let is_unconverted = CHANGED_FAMILIES
+                    .iter()
+                    .any(|&(changed_in, prefix)| changed(changed_in) && attr.starts_with(prefix));
+
+                if is_unconverted {
+                    reporter.item_caveat(
+                        item,
+                        format!(
+                            "could not migrate the attribute {attr:?}, so it was left unchanged"
+                        ),
+                    );
+                }
+
+                return Some(attr);
+            }
+
+            // Re-rendering any other attribute could alter its text, such as the string literal of
+            // `#[must_use = r"message"]`, or convert a form that `from_version` never used, such
+            // as `#[repr(C)]` in v42.
+            let is_old_form = CHANGED_FAMILIES.iter().any(|&(changed_in, prefix)| {
+                attribute.changed_in() == Some(changed_in)
+                    && changed(changed_in)
+                    && attr.starts_with(prefix)
+            });
+
+            if !is_old_form {
+                return Some(attr);
+            }
+
+            attribute.render(to_version)
+        })
+        .collect()
+}
+
+/// Converts an attribute of any format version to the form used by the newest format version.
+pub(crate) fn normalize(attr: &str) -> Option<String> {
+    migrate(attr.to_owned(), u32::MAX)
+}
+
+impl Repr {
+    /// Returns the [`Debug`] form of this `#[repr(...)]` argument, used before v43.
+    fn debug(&self) -> String {
+        match self {
+            Self::Int(int_type) => {
+                let (kind, prefix) = match int_type {
+                    IntType::Signed(_) => ("SignedInt", "I"),
+                    IntType::Unsigned(_) => ("UnsignedInt", "U"),
+                };
+
+                let size = match int_type {
+                    IntType::Signed(size) | IntType::Unsigned(size) => size,
+                };
+
+                let size = match size {
+                    IntSize::Size => "size",
+                    IntSize::X8 => "8",
+                    IntSize::X16 => "16",
+                    IntSize::X32 => "32",
+                    IntSize::X64 => "64",
+                    IntSize::X128 => "128",
+                };
+
+                format!("ReprInt({kind}({prefix}{size}))")
+            }
+            Self::Rust => "ReprRust".to_owned(),
+            Self::C => "ReprC".to_owned(),
+            Self::Packed(align) => format!("ReprPacked(Align({align} bytes))"),
+            Self::Simd => "ReprSimd".to_owned(),
+            Self::Transparent => "ReprTransparent".to_owned(),
+            Self::Align(align) => format!("ReprAlign(Align({align} bytes))"),
+        }
+    }
+}
+
+/// Formats the pretty-printed form of this `#[repr(...)]` argument, used since v43.
+impl fmt::Display for Repr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(int_type) => write!(f, "{int_type}"),
+            Self::Rust => write!(f, "Rust"),
+            Self::C => write!(f, "C"),
+            Self::Packed(align) => write!(f, "packed({align})"),
+            Self::Simd => write!(f, "simd"),
+            Self::Transparent => write!(f, "transparent"),
+            Self::Align(align) => write!(f, "align({align})"),
+        }
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed(int_size) => write!(f, "i{int_size}"),
+            Self::Unsigned(int_size) => write!(f, "u{int_size}"),
+        }
+    }
+}
+
+impl fmt::Display for IntSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Size => write!(f, "size"),
+            Self::X8 => write!(f, "8"),
+            Self::X16 => write!(f, "16"),
+            Self::X32 => write!(f, "32"),
+            Self::X64 => write!(f, "64"),
+            Self::X128 => write!(f, "128"),
+        }
+    }
+}
+
+/// Parses a Rust string literal, such as `"message"` or `r#"message"#`, returning its unescaped
+/// value.
+///
+/// Returns `None` if `literal` is not exactly one valid string literal.
+fn parse_string_literal(literal: &str) -> Option<String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = &raw[..raw.len() - raw.trim_start_matches('#').len()];
+        let terminator = format!("\"{hashes}");
+
+        let value = raw[hashes.len()..]
+            .strip_prefix('"')?
+            .strip_suffix(terminator.as_str())?;
+
+        // Raw strings end at the first terminator, so it cannot appear in their value.
+        if value.contains(terminator.as_str()) {
+            return None;
+        }
+
+        return Some(value.to_owned());
+    }
+
+    let mut chars = literal.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut value = String::new();
+
+    while let Some(c) = chars.next() {
+        let unescaped = match c {
+            // An unescaped quote would have ended the string literal early.
+            '"' => return None,
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                '\\' => '\\',
+                '0' => '\0',
+                '\'' => '\'',
+                '"' => '"',
+                'x' => {
+                    let digits = chars.as_str().get(..2)?;
+
+                    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        return None;
+                    }
+
+                    let code = u8::from_str_radix(digits, 16).ok()?;
+
+                    // `\x` escapes in string literals are limited to ASCII.
+                    if code > 0x7F {
+                        return None;
+                    }
+
+                    chars.nth(1);
+                    char::from(code)
+                }
+                'u' => {
+                    let rest = chars.as_str().strip_prefix('{')?;
+                    let (digits, _) = rest.split_once('}')?;
+
+                    // Underscores may separate the digits, such as `\u{1_F600}`.
+                    let hex: String = digits.chars().filter(|&c| c != '_').collect();
+
+                    if hex.is_empty()
+                        || hex.len() > 6
+                        || !hex.bytes().all(|b| b.is_ascii_hexdigit())
+                    {
+                        return None;
+                    }
+
+                    let c = char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?;
+
+                    // Skip `{`, the digits, and `}`.
+                    chars.nth(digits.len() + 1);
+                    c
+                }
+                // A backslash at the end of a line skips the newline and any leading whitespace
+                // on the next line.
+                '\n' => {
+                    let rest = chars.as_str().trim_start_matches([' ', '\t', '\n', '\r']);
+                    chars = rest.chars();
+                    continue;
+                }
+                _ => return None,
+            },
+            c => c,
+        };
+
+        value.push(unescaped);
+    }
+
+    Some(value)
+}
+
+/// Parses the [`Debug`] form of `#[repr(...)]`, such as `#[attr = Repr([ReprC])]\n`.
+///
+/// `ReprEmpty` is skipped, as it has no pretty-printed form.
+fn parse_debug_reprs(input: &mut &str) -> Result<Vec<Repr>> {
+    let reprs: Vec<Option<Repr>> = delimited(
+        "#[attr = Repr([",
+        separated(1.., parse_debug_repr, ", "),
+        "])]\n",
+    )
+    .parse_next(input)?;
+
+    Ok(reprs.into_iter().flatten().collect())
+}
+
+fn parse_debug_repr(input: &mut &str) -> Result<Option<Repr>> {
+    dispatch! { alpha1;
+        "ReprInt" => delimited("(", parse_debug_int_type, ")").map(|int| Some(Repr::Int(int))),
+        "ReprRust" => empty.value(Some(Repr::Rust)),
+        "ReprC" => empty.value(Some(Repr::C)),
+        "ReprPacked" => delimited("(", parse_debug_align, ")").map(|align| Some(Repr::Packed(align))),
+        "ReprSimd" => empty.value(Some(Repr::Simd)),
+        "ReprTransparent" => empty.value(Some(Repr::Transparent)),
+        "ReprAlign" => delimited("(", parse_debug_align, ")").map(|align| Some(Repr::Align(align))),
+        // This shouldn't realistically ever be hit.
+        "ReprEmpty" => empty.value(None),
+        _ => fail::<_, _, _>,
+    }
+    .parse_next(input)
+}
+
+fn parse_debug_int_type(input: &mut &str) -> Result<IntType> {
+    dispatch! { alpha1;
+        "SignedInt" => delimited("(", parse_debug_int_ty, ")").map(IntType::Signed),
+        "UnsignedInt" => delimited("(", parse_debug_uint_ty, ")").map(IntType::Unsigned),
+        _ => fail::<_, _, _>,
+    }
+    .parse_next(input)
+}
+
+fn parse_debug_int_ty(input: &mut &str) -> Result<IntSize> {
+    dispatch! { alphanumeric1;
+        "Isize" => empty.value(IntSize::Size),
+        "I8" => empty.value(IntSize::X8),
+        "I16" => empty.value(IntSize::X16),
+        "I32" => empty.value(IntSize::X32),
+        "I64" => empty.value(IntSize::X64),
+        "I128" => empty.value(IntSize::X128),
+        _ => fail::<_, _, _>,
+    }
+    .parse_next(input)
+}
+
+fn parse_debug_uint_ty(input: &mut &str) -> Result<IntSize> {
+    dispatch! { alphanumeric1;
+        "Usize" => empty.value(IntSize::Size),
+        "U8" => empty.value(IntSize::X8),
+        "U16" => empty.value(IntSize::X16),
+        "U32" => empty.value(IntSize::X32),
+        "U64" => empty.value(IntSize::X64),
+        "U128" => empty.value(IntSize::X128),
+        _ => fail::<_, _, _>,
+    }
+    .parse_next(input)
+}
+
+fn parse_debug_align(input: &mut &str) -> Result<u64> {
+    delimited("Align(", digit1.parse_to(), " bytes)").parse_next(input)
+}
+
+/// Parses the pretty-printed form of `#[repr(...)]`, such as `#[repr(C, align(8))]`.
+fn parse_pretty_reprs(input: &mut &str) -> Result<Vec<Repr>> {
+    delimited(
+        "#[repr(",
+        separated(1.., parse_pretty_repr, (",", space0)),
+        ")]",
+    )
+    .parse_next(input)
+}
+
+fn parse_pretty_repr(input: &mut &str) -> Result<Repr> {
+    dispatch! { alphanumeric1;
+        "Rust" => empty.value(Repr::Rust),
+        "C" => empty.value(Repr::C),
+        "packed" => delimited("(", digit1.parse_to(), ")").map(Repr::Packed),
+        "simd" => empty.value(Repr::Simd),
+        "transparent" => empty.value(Repr::Transparent),
+        "align" => delimited("(", digit1.parse_to(), ")").map(Repr::Align),
+        "isize" => empty.value(Repr::Int(IntType::Signed(IntSize::Size))),
+        "i8" => empty.value(Repr::Int(IntType::Signed(IntSize::X8))),
+        "i16" => empty.value(Repr::Int(IntType::Signed(IntSize::X16))),
+        "i32" => empty.value(Repr::Int(IntType::Signed(IntSize::X32))),
+        "i64" => empty.value(Repr::Int(IntType::Signed(IntSize::X64))),
+        "i128" => empty.value(Repr::Int(IntType::Signed(IntSize::X128))),
+        "usize" => empty.value(Repr::Int(IntType::Unsigned(IntSize::Size))),
+        "u8" => empty.value(Repr::Int(IntType::Unsigned(IntSize::X8))),
+        "u16" => empty.value(Repr::Int(IntType::Unsigned(IntSize::X16))),
+        "u32" => empty.value(Repr::Int(IntType::Unsigned(IntSize::X32))),
+        "u64" => empty.value(Repr::Int(IntType::Unsigned(IntSize::X64))),
+        "u128" => empty.value(Repr::Int(IntType::Unsigned(IntSize::X128))),
+        _ => fail::<_, _, _>,
+    }
+    .parse_next(input)
+}
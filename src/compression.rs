@@ -0,0 +1,146 @@
+//! Transparent (de)compression of the input and output JSON.
+
+use std::{
+    ffi::OsStr,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use anyhow::Context;
+use flate2::{read::MultiGzDecoder, write::GzEncoder};
+
+/// A compression format supported by `--input` and `--output`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// The first bytes of every Gzip file.
+    const GZIP_MAGIC_BYTES: &[u8] = &[0x1f, 0x8b];
+
+    /// The first bytes of every Zstandard frame.
+    const ZSTD_MAGIC_BYTES: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+    /// Detects the compression format from a path's extension, such as `crate.json.gz`.
+    ///
+    /// Unknown extensions, including `.json`, are assumed to be uncompressed.
+    pub fn from_extension(path: &Path) -> Self {
+        match path.extension().and_then(OsStr::to_str) {
+            Some("gz") => Self::Gzip,
+            Some("zst") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    /// Detects the compression format from the first bytes of a file.
+    pub fn from_magic_bytes(bytes: &[u8]) -> Self {
+        if bytes.starts_with(Self::GZIP_MAGIC_BYTES) {
+            Self::Gzip
+        } else if bytes.starts_with(Self::ZSTD_MAGIC_BYTES) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// Opens the `--input` file, decompressing it if necessary.
+///
+/// The compression format is detected from the file's extension, falling back to its magic bytes.
+pub fn open_input(path: &Path) -> anyhow::Result<Box<dyn Read>> {
+    let file = File::open(path)
+        .with_context(|| format!("could not read `--input` file: {}", path.display()))?;
+
+    let mut reader = BufReader::new(file);
+
+    let compression = match Compression::from_extension(path) {
+        Compression::None => Compression::from_magic_bytes(
+            reader
+                .fill_buf()
+                .with_context(|| format!("could not read `--input` file: {}", path.display()))?,
+        ),
+        compression => compression,
+    };
+
+    let reader: Box<dyn Read> = match compression {
+        Compression::None => Box::new(reader),
+        Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(reader))),
+        Compression::Zstd => Box::new(BufReader::new(
+            zstd::Decoder::with_buffer(reader)
+                .context("failed to start Zstandard decompression")?,
+        )),
+    };
+
+    Ok(reader)
+}
+
+/// The destination of the migrated JSON.
+pub enum Output {
+    Stdout(BufWriter<io::Stdout>),
+    File(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+impl Output {
+    /// Creates the `--output` file, compressing it based on its extension.
+    ///
+    /// If `path` is `None`, the JSON is written to `stdout` uncompressed.
+    pub fn create(path: Option<&Path>) -> anyhow::Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::Stdout(BufWriter::new(io::stdout())));
+        };
+
+        let file = File::create(path)
+            .with_context(|| format!("could not create `--output` file: {}", path.display()))?;
+
+        let writer = BufWriter::new(file);
+
+        let output = match Compression::from_extension(path) {
+            Compression::None => Self::File(writer),
+            Compression::Gzip => Self::Gzip(GzEncoder::new(writer, flate2::Compression::default())),
+            Compression::Zstd => Self::Zstd(
+                zstd::Encoder::new(writer, zstd::DEFAULT_COMPRESSION_LEVEL)
+                    .context("failed to start Zstandard compression")?,
+            ),
+        };
+
+        Ok(output)
+    }
+
+    /// Finishes compressing the output, if necessary, and flushes it.
+    ///
+    /// This must be called once all JSON has been written, else the output may be truncated.
+    pub fn finish(self) -> io::Result<()> {
+        match self {
+            Self::Stdout(mut writer) => writer.flush(),
+            Self::File(mut writer) => writer.flush(),
+            Self::Gzip(encoder) => encoder.finish()?.flush(),
+            Self::Zstd(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Stdout(writer) => writer.write(buf),
+            Self::File(writer) => writer.write(buf),
+            Self::Gzip(encoder) => encoder.write(buf),
+            Self::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stdout(writer) => writer.flush(),
+            Self::File(writer) => writer.flush(),
+            Self::Gzip(encoder) => encoder.flush(),
+            Self::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! Filters that prune items from the migrated `Crate`.
+//!
+//! Filters operate on the JSON [`Value`] of a `Crate`, rather than on a typed `Crate`, so that they
+//! work with every format version.
+//!
+//! After items are removed from `index`, every reference to them is rewritten so that the `Crate`
+//! remains valid:
+//!
+//! - They are removed from the lists that contain them, such as `Module::items` and
+//!   `Enum::variants`, and `has_stripped_fields` or `has_stripped_variants` is set where relevant.
+//! - Removed tuple fields are replaced with `null`, as Rustdoc does for stripped fields.
+//! - Intra-doc links to them are removed from `Item::links`.
+//! - `Use::id` is set to `null` if it no longer points to an item in `index` or `paths`.
+//! - Entries in `paths` and `external_crates` that are no longer referenced are removed.
+
+use std::{collections::HashSet, fmt};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::ids::{
+    CHILD_LISTS, Id, LOCAL_CRATE_ID, children, object, object_mut, visit_objects, visit_objects_mut,
+};
+
+/// Which items should be removed from the migrated `Crate`.
+///
+/// The default filter keeps every item. A filter is displayed as the arguments that enable it,
+/// such as `--strip-private --only-module my_crate::foo`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Filter {
+    /// Whether items that are not public should be removed, along with their children.
+    ///
+    /// This also sets `Crate::includes_private` to false.
+    pub strip_private: bool,
+
+    /// Whether entries in `Crate::paths` from other crates should be removed, unless they are
+    /// referenced by an item in `Crate::index`.
+    pub strip_external_paths: bool,
+
+    /// The path of a module, such as `my_crate::foo::bar`, whose items are the only ones that
+    /// should be kept.
+    ///
+    /// The modules between the crate root and this module are kept, but only contain the next
+    /// module in the path.
+    pub only_module: Option<String>,
+}
+
+impl Filter {
+    /// Returns true if this filter would not remove anything.
+    pub fn is_empty(&self) -> bool {
+        !self.strip_private && !self.strip_external_paths && self.only_module.is_none()
+    }
+
+    /// Removes the items in `crate_` that do not pass this filter.
+    pub fn apply(&self, crate_: &mut Value) -> anyhow::Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let root = crate_["root"]
+            .as_u64()
+            .context("`Crate::root` is not an ID")?;
+
+        let index = object(crate_, "index")?;
+
+        let mut removed = HashSet::new();
+
+        if self.strip_private {
+            let private = index
+                .iter()
+                .filter(|(_, item)| !is_public(item))
+                .filter_map(|(id, _)| id.parse::<Id>().ok())
+                .collect();
+
+            // The children of private items would be unreachable, so remove them too.
+            removed.extend(descendants(index, private));
+        }
+
+        if let Some(module) = &self.only_module {
+            let kept = module_subtree(index, object(crate_, "paths")?, module)?;
+
+            removed.extend(
+                index
+                    .keys()
+                    .filter_map(|id| id.parse::<Id>().ok())
+                    .filter(|id| !kept.contains(id)),
+            );
+        }
+
+        // The crate root can never be removed.
+        removed.remove(&root);
+
+        remove_items(crate_, &removed, self.strip_external_paths)?;
+
+        if self.strip_private {
+            crate_["includes_private"] = Value::Bool(false);
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            strip_private,
+            strip_external_paths,
+            only_module,
+        } = self;
+
+        let mut args = Vec::new();
+
+        if *strip_private {
+            args.push("--strip-private".to_owned());
+        }
+
+        if *strip_external_paths {
+            args.push("--strip-external-paths".to_owned());
+        }
+
+        if let Some(module) = only_module {
+            args.push(format!("--only-module {module}"));
+        }
+
+        f.write_str(&args.join(" "))
+    }
+}
+
+/// Returns true if the item's `visibility` is `public` or `default`.
+///
+/// `default` is used by items whose visibility is inherited from their parent, such as enum
+/// variants and trait items.
+fn is_public(item: &Value) -> bool {
+    matches!(item["visibility"].as_str(), Some("public" | "default"))
+}
+
+/// Extends `ids` with all children of the items in it, recursively.
+fn descendants(index: &Map<String, Value>, ids: HashSet<Id>) -> HashSet<Id> {
+    let mut stack: Vec<Id> = ids.iter().copied().collect();
+    let mut ids = ids;
+
+    while let Some(id) = stack.pop() {
+        let Some(item) = index.get(&id.to_string()) else {
+            continue;
+        };
+
+        for child in children(item) {
+            if ids.insert(child) {
+                stack.push(child);
+            }
+        }
+    }
+
+    ids
+}
+
+/// Returns the IDs of the items kept by [`Filter::only_module`]: the module, its descendants, and
+/// the modules between it and the crate root.
+fn module_subtree(
+    index: &Map<String, Value>,
+    paths: &Map<String, Value>,
+    module: &str,
+) -> anyhow::Result<HashSet<Id>> {
+    let segments: Vec<&str> = module.split("::").collect();
+
+    // Finds the ID of the local module with the given path.
+    let find_module = |path: &[&str]| {
+        paths.iter().find_map(|(id, summary)| {
+            let is_module = summary["crate_id"].as_u64() == Some(LOCAL_CRATE_ID)
+                && summary["kind"].as_str() == Some("module")
+                && summary["path"].as_array().is_some_and(|p| {
+                    p.iter()
+                        .map(Value::as_str)
+                        .eq(path.iter().map(|s| Some(*s)))
+                });
+
+            if is_module {
+                id.parse::<Id>().ok()
+            } else {
+                None
+            }
+        })
+    };
+
+    let target = find_module(&segments).with_context(|| {
+        format!("could not find the module `{module}` passed to `--only-module`")
+    })?;
+
+    let mut kept = descendants(index, HashSet::from([target]));
+
+    for len in 1..segments.len() {
+        kept.extend(find_module(&segments[..len]));
+    }
+
+    Ok(kept)
+}
+
+/// Removes the `removed` items from `Crate::index`, and rewrites every reference to them as
+/// described in the [module documentation](self).
+///
+/// If `strip_external_paths` is true, entries in `Crate::paths` from other crates that are not
+/// referenced are removed too.
+pub(crate) fn remove_items(
+    crate_: &mut Value,
+    removed: &HashSet<Id>,
+    strip_external_paths: bool,
+) -> anyhow::Result<()> {
+    let root = crate_["root"]
+        .as_u64()
+        .context("`Crate::root` is not an ID")?;
+
+    let index = object_mut(crate_, "index")?;
+
+    index.retain(|id, _| !id.parse::<Id>().is_ok_and(|id| removed.contains(&id)));
+
+    for item in index.values_mut() {
+        remove_references(item, removed);
+    }
+
+    let index_ids: HashSet<Id> = index.keys().filter_map(|id| id.parse().ok()).collect();
+
+    let mut referenced = HashSet::from([root]);
+
+    for item in index.values() {
+        collect_references(item, &mut referenced);
+    }
+
+    let paths = object_mut(crate_, "paths")?;
+
+    paths.retain(|id, summary| {
+        let Ok(id) = id.parse::<Id>() else {
+            return true;
+        };
+
+        if index_ids.contains(&id) || referenced.contains(&id) {
+            return true;
+        }
+
+        let is_external = summary["crate_id"].as_u64() != Some(LOCAL_CRATE_ID);
+        let is_stripped = strip_external_paths && is_external;
+
+        !(removed.contains(&id) || is_stripped)
+    });
+
+    let path_ids: HashSet<Id> = paths.keys().filter_map(|id| id.parse().ok()).collect();
+
+    // Collect the crates still in use, after all items have been pruned.
+    let mut crate_ids = HashSet::new();
+
+    for key in ["index", "paths"] {
+        crate_ids.extend(
+            object(crate_, key)?
+                .values()
+                .filter_map(|value| value["crate_id"].as_u64()),
+        );
+    }
+
+    for item in object_mut(crate_, "index")?.values_mut() {
+        clear_dangling_use(item, &index_ids, &path_ids);
+    }
+
+    object_mut(crate_, "external_crates")?
+        .retain(|id, _| id.parse::<u64>().is_ok_and(|id| crate_ids.contains(&id)));
+
+    Ok(())
+}
+
+/// Removes references to `removed` items from an item's child lists and links.
+fn remove_references(item: &mut Value, removed: &HashSet<Id>) {
+    let is_removed = |id: &Value| id.as_u64().is_some_and(|id| removed.contains(&id));
+
+    if let Some(links) = item.get_mut("links").and_then(Value::as_object_mut) {
+        links.retain(|_, id| !is_removed(id));
+    }
+
+    let Some(inner) = item.get_mut("inner") else {
+        return;
+    };
+
+    visit_objects_mut(inner, &mut |object| {
+        for key in CHILD_LISTS {
+            let Some(Value::Array(ids)) = object.get_mut(*key) else {
+                continue;
+            };
+
+            let len = ids.len();
+            ids.retain(|id| !is_removed(id));
+
+            if ids.len() == len {
+                continue;
+            }
+
+            // Tell consumers that the list is incomplete.
+            let stripped_key = match *key {
+                "fields" => "has_stripped_fields",
+                "variants" => "has_stripped_variants",
+                _ => continue,
+            };
+
+            if let Some(stripped) = object.get_mut(stripped_key) {
+                *stripped = Value::Bool(true);
+            }
+        }
+
+        // Tuple fields are `Option<Id>`s, where `None` is a stripped field. This is also used for
+        // tuples of types, but those never contain plain IDs.
+        if let Some(Value::Array(fields)) = object.get_mut("tuple") {
+            for field in fields.iter_mut().filter(|field| is_removed(field)) {
+                *field = Value::Null;
+            }
+        }
+    });
+}
+
+/// Collects the IDs an item refers to, including its children, links, and paths in its types.
+fn collect_references(item: &Value, referenced: &mut HashSet<Id>) {
+    if let Some(links) = item["links"].as_object() {
+        referenced.extend(links.values().filter_map(Value::as_u64));
+    }
+
+    // The module that `pub(in path)` items are visible in.
+    referenced.extend(
+        item.pointer("/visibility/restricted/parent")
+            .and_then(Value::as_u64),
+    );
+
+    referenced.extend(children(item));
+
+    visit_objects(&item["inner"], &mut |object| {
+        referenced.extend(object.get("id").and_then(Value::as_u64));
+    });
+}
+
+/// Sets `Use::id` to `null` if the item it imports is in neither `index` nor `paths`.
+fn clear_dangling_use(item: &mut Value, index_ids: &HashSet<Id>, path_ids: &HashSet<Id>) {
+    let Some(id) = item.pointer_mut("/inner/use/id") else {
+        return;
+    };
+
+    if id
+        .as_u64()
+        .is_some_and(|id| !index_ids.contains(&id) && !path_ids.contains(&id))
+    {
+        *id = Value::Null;
+    }
+}
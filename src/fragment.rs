@@ -0,0 +1,43 @@
+//! Fragments of a `Crate`, such as a single `Item` or `Type`, that can be migrated on their own.
+//!
+//! See [`migrations::migrate_fragment()`](crate::migrations::migrate_fragment).
+
+use anyhow::Context;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::{reporter::Reporter, traits::MigrateUp};
+
+/// The `rustdoc_types` type of a JSON fragment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FragmentKind {
+    /// An `Item` from `Crate::index`.
+    Item,
+    /// An `ItemSummary` from `Crate::paths`.
+    ItemSummary,
+    Type,
+    Generics,
+    GenericArgs,
+    GenericBound,
+    Path,
+    FunctionSignature,
+    Span,
+    Visibility,
+}
+
+/// Migrates a JSON fragment as `T` to the next format version.
+///
+/// This is used by `declare_migrate_up!`, and is not part of the public API.
+#[doc(hidden)]
+pub fn migrate_as<T>(fragment: Value, reporter: &mut Reporter) -> anyhow::Result<Value>
+where
+    T: DeserializeOwned + MigrateUp<Up: Serialize>,
+{
+    let name = std::any::type_name::<T>().rsplit("::").next().unwrap();
+
+    let current: T = serde_json::from_value(fragment)
+        .with_context(|| format!("failed to deserialize `{name}` from JSON"))?;
+
+    serde_json::to_value(current.migrate_up(reporter))
+        .with_context(|| format!("failed to serialize `{name}` to JSON"))
+}
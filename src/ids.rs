@@ -0,0 +1,127 @@
+//! Helpers for finding and rewriting the `Id`s within the JSON [`Value`] of a `Crate`.
+//!
+//! These only rely on fields that have not changed shape between the supported format versions, so
+//! they work with every format version.
+
+use anyhow::Context;
+use serde_json::{Map, Value};
+
+/// The ID of an item in `Crate::index` or `Crate::paths`.
+pub type Id = u64;
+
+/// The keys of lists that contain the IDs of child items, such as `Module::items`.
+pub const CHILD_LISTS: &[&str] = &["items", "fields", "variants", "impls", "implementations"];
+
+/// The `crate_id` of the crate being documented.
+pub const LOCAL_CRATE_ID: u64 = 0;
+
+/// Returns the object stored in `key` of the `Crate`.
+pub fn object<'a>(crate_: &'a Value, key: &str) -> anyhow::Result<&'a Map<String, Value>> {
+    crate_
+        .get(key)
+        .and_then(Value::as_object)
+        .with_context(|| format!("`Crate::{key}` is not an object"))
+}
+
+/// Returns the object stored in `key` of the `Crate`, mutably.
+pub fn object_mut<'a>(
+    crate_: &'a mut Value,
+    key: &str,
+) -> anyhow::Result<&'a mut Map<String, Value>> {
+    crate_
+        .get_mut(key)
+        .and_then(Value::as_object_mut)
+        .with_context(|| format!("`Crate::{key}` is not an object"))
+}
+
+/// Returns the IDs listed in the child lists of an item, such as `Module::items`.
+pub fn children(item: &Value) -> Vec<Id> {
+    let mut children = Vec::new();
+
+    visit_objects(&item["inner"], &mut |object| {
+        for key in CHILD_LISTS.iter().copied().chain(["tuple"]) {
+            if let Some(Value::Array(ids)) = object.get(key) {
+                children.extend(ids.iter().filter_map(Value::as_u64));
+            }
+        }
+    });
+
+    children
+}
+
+/// Replaces every ID within an item with the result of `f`.
+///
+/// This includes the item's own ID, its links, its children, and the paths in its types.
+pub fn map_ids(item: &mut Value, f: &mut impl FnMut(Id) -> Id) {
+    let mut map = |id: &mut Value| {
+        if let Some(old) = id.as_u64() {
+            *id = Value::from(f(old));
+        }
+    };
+
+    if let Some(id) = item.get_mut("id") {
+        map(id);
+    }
+
+    if let Some(links) = item.get_mut("links").and_then(Value::as_object_mut) {
+        links.values_mut().for_each(&mut map);
+    }
+
+    if let Some(parent) = item.pointer_mut("/visibility/restricted/parent") {
+        map(parent);
+    }
+
+    let Some(inner) = item.get_mut("inner") else {
+        return;
+    };
+
+    visit_objects_mut(inner, &mut |object| {
+        if let Some(id) = object.get_mut("id") {
+            map(id);
+        }
+
+        for key in CHILD_LISTS.iter().copied().chain(["tuple"]) {
+            if let Some(Value::Array(ids)) = object.get_mut(key) {
+                ids.iter_mut().for_each(&mut map);
+            }
+        }
+    });
+}
+
+/// Calls `f` on every object within `value`, including `value` itself.
+pub fn visit_objects(value: &Value, f: &mut impl FnMut(&Map<String, Value>)) {
+    match value {
+        Value::Object(object) => {
+            f(object);
+
+            for value in object.values() {
+                visit_objects(value, f);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                visit_objects(value, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Calls `f` on every object within `value`, including `value` itself.
+pub fn visit_objects_mut(value: &mut Value, f: &mut impl FnMut(&mut Map<String, Value>)) {
+    match value {
+        Value::Object(object) => {
+            f(object);
+
+            for value in object.values_mut() {
+                visit_objects_mut(value, f);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                visit_objects_mut(value, f);
+            }
+        }
+        _ => {}
+    }
+}
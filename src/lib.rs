@@ -0,0 +1,41 @@
+//! Migrate Rustdoc's JSON output to newer format versions.
+//!
+//! This library powers the `migrate_rustdoc_json` CLI. The main entrypoint is
+//! [`migrations::migrate_up()`], which migrates a Rustdoc JSON string to a newer format version.
+//! To work with typed `Crate`s instead, use [`traits::MigrateTo`] or
+//! [`migrations::VersionedCrate`]. Fragments of a `Crate`, such as a single `Item`, can be migrated
+//! with [`migrations::migrate_fragment()`]. Tools that only read a `Crate` can use the [`view`]
+//! traits, which work with every format version without migrating. The [`schema`] module provides
+//! a JSON Schema for each format version, and validates JSON against it.
+//!
+//! # Features
+//!
+//! Each format version is gated behind a feature of the same name, such as `v45`, as compiling
+//! every version of `rustdoc_types` is slow. `min-vXX` features enable format version `vXX` and
+//! every version after it. By default, all format versions are enabled with `min-v41`.
+//!
+//! A migration is only available if the features for both its format versions are enabled, so
+//! migrating from v45 to v48 requires `v45`, `v46`, and `v48`. Use
+//! [`migrations::SUPPORTED_VERSIONS`] to check which format versions are enabled.
+//!
+//! The `parallel` feature migrates the items of `Crate::index` and `Crate::paths` across multiple
+//! threads with [Rayon](https://docs.rs/rayon), which speeds up migrating large crates. It is
+//! disabled by default.
+
+pub mod args;
+pub mod attributes;
+pub mod compression;
+pub mod filter;
+pub mod fragment;
+mod ids;
+mod macros;
+pub mod manifest;
+pub mod merge;
+pub mod migrations;
+pub mod output;
+mod primitives;
+pub mod reporter;
+pub mod schema;
+pub mod traits;
+pub mod version;
+pub mod view;
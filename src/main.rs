@@ -1,17 +1,20 @@
-use std::process::ExitCode;
+use std::{io::Write, process::ExitCode};
 
-use anstream::println;
+use anstream::{print, println};
+use anstyle::{AnsiColor, Color, Style};
 use anyhow::Context;
-
-use self::reporter::Reporter;
-
-mod args;
-mod macros;
-mod migrations;
-mod primitives;
-mod reporter;
-mod traits;
-mod version;
+use migrate_rustdoc_json::{
+    args::{
+        self, Command, MergeArgs, MigrateArgs, ProvenanceArgs, SchemaArgs, SchemaDiffArgs,
+        ValidateArgs,
+    },
+    compression::{self, Output},
+    manifest::{self, Manifest},
+    merge, migrations,
+    output::{self, OutputOptions},
+    reporter::Reporter,
+    schema, version,
+};
 
 /// The main entrypoint with a custom error handler.
 ///
@@ -35,14 +38,210 @@ fn migrate_rustdoc_json(reporter: &mut Reporter) -> anyhow::Result<()> {
 
     reporter.configure(&args);
 
-    let input = std::fs::read_to_string(&args.input)
-        .with_context(|| format!("could not read `--input` file: {}", args.input.display()))?;
+    match &args.command {
+        Command::Migrate(args) => migrate(args, reporter),
+        Command::Merge(args) => merge(args, reporter),
+        Command::Provenance(args) => provenance(args),
+        Command::Schema(args) => print_schema(args),
+        Command::Validate(args) => validate(args),
+        Command::SchemaDiff(args) => schema_diff(args),
+    }
+}
+
+/// Migrates the `--input` JSON, the default subcommand.
+fn migrate(args: &MigrateArgs, reporter: &mut Reporter) -> anyhow::Result<()> {
+    // The output may overwrite the input, so the input is hashed before it is migrated.
+    let source_sha256 = if args.manifest {
+        Some(manifest::hash_file(&args.input)?)
+    } else {
+        None
+    };
+
+    let mut output = migrations::migrate_up_streaming(
+        || compression::open_input(&args.input),
+        || Output::create(args.output.as_deref()),
+        args.from_version,
+        args.lenient,
+        args.to_version.format_version(),
+        &args.output_options,
+        reporter,
+    )?;
+
+    writeln!(output).context("failed to write the migrated JSON")?;
+    output
+        .finish()
+        .context("failed to write the migrated JSON")?;
+
+    if let Some(source_sha256) = source_sha256 {
+        // `--manifest` requires `--output`, which is checked while parsing the arguments.
+        let output_path = args.output.as_deref().unwrap();
 
-    let output = self::migrations::migrate_up(&input, args.to_version.format_version(), reporter)?;
+        Manifest::new(source_sha256, &args.output_options.filter, reporter)?
+            .write(&Manifest::path_for(output_path))?;
+    }
 
     reporter.print_success_report();
 
-    println!("{output}");
+    Ok(())
+}
+
+/// Migrates several crates to a common format version and merges them, the `merge` subcommand.
+fn merge(args: &MergeArgs, reporter: &mut Reporter) -> anyhow::Result<()> {
+    let mut crates = Vec::with_capacity(args.inputs.len());
+
+    for input in &args.inputs {
+        let json = migrations::migrate_up_streaming(
+            || compression::open_input(input),
+            || Ok(Vec::new()),
+            None,
+            false,
+            args.to_version.format_version(),
+            &OutputOptions::default(),
+            reporter,
+        )
+        .with_context(|| format!("failed to migrate `{}`", input.display()))?;
+
+        crates.push(serde_json::from_slice(&json).context("failed to parse the migrated JSON")?);
+    }
+
+    let workspace = merge::merge(crates).context("failed to merge the migrated crates")?;
+
+    let mut output = Output::create(args.output.as_deref())?;
+
+    output::to_writer(&mut output, &workspace, &args.output_options)?;
+    writeln!(output).context("failed to write the merged JSON")?;
+    output.finish().context("failed to write the merged JSON")?;
+
+    reporter.print_success_report();
+
+    Ok(())
+}
+
+/// Prints the migration manifest of a migrated file, the `provenance` subcommand.
+fn provenance(args: &ProvenanceArgs) -> anyhow::Result<()> {
+    // Accept either the manifest itself or the migrated file it describes.
+    let path = if args.file.to_string_lossy().ends_with(Manifest::SUFFIX) {
+        args.file.clone()
+    } else {
+        Manifest::path_for(&args.file)
+    };
+
+    anyhow::ensure!(
+        path.exists(),
+        "`{}` has no migration manifest, it may not have been migrated or was migrated without `--manifest`",
+        args.file.display(),
+    );
+
+    let manifest = Manifest::read(&path)?;
+
+    let blue = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Blue)));
+    let bold = Style::new().bold();
+    let dim = Style::new().dimmed();
+
+    println!(
+        "{blue}Migrated by {bold}{tool} v{tool_version}{bold:#}{blue:#}",
+        tool = manifest.tool,
+        tool_version = manifest.tool_version,
+    );
+    println!(
+        "\tfrom {bold}v{source_version}{bold:#} {dim}(sha256 {sha256}){dim:#}",
+        source_version = manifest.source_version,
+        sha256 = manifest.source_sha256,
+    );
+    println!(
+        "\tto {bold}v{target_version}{bold:#}",
+        target_version = manifest.target_version,
+    );
+
+    if !manifest.hops.is_empty() {
+        let hops: Vec<String> = manifest
+            .hops
+            .iter()
+            .map(|hop| format!("v{} to v{}", hop.from, hop.to))
+            .collect();
+
+        println!("\t{dim}via {hops}{dim:#}", hops = hops.join(", "));
+    }
+
+    if !manifest.filter.is_empty() {
+        println!(
+            "\tfiltered with {bold}{filter}{bold:#}",
+            filter = manifest.filter,
+        );
+    }
+
+    if !manifest.caveats.is_empty() {
+        let yellow = Style::new()
+            .fg_color(Some(Color::Ansi(AnsiColor::Yellow)))
+            .bold();
+
+        println!("\n{yellow}Caveats:{yellow:#}");
+
+        for caveat in &manifest.caveats {
+            println!(
+                "\t- {msg} {dim}({location}){dim:#}",
+                msg = caveat.message,
+                location = caveat.location(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the JSON Schema of a format version, the `schema` subcommand.
+fn print_schema(args: &SchemaArgs) -> anyhow::Result<()> {
+    let schema = schema::schema(args.version.format_version())?;
+
+    let mut output = Output::create(args.output.as_deref())?;
+
+    serde_json::to_writer_pretty(&mut output, &schema).context("failed to write the schema")?;
+    writeln!(output).context("failed to write the schema")?;
+    output.finish().context("failed to write the schema")?;
+
+    Ok(())
+}
+
+/// Validates Rustdoc JSON against the schema of its format version, the `validate` subcommand.
+fn validate(args: &ValidateArgs) -> anyhow::Result<()> {
+    let format_version = version::detect_version(compression::open_input(&args.file)?)?;
+
+    let json: serde_json::Value = serde_json::from_reader(compression::open_input(&args.file)?)
+        .context("failed to parse JSON")?;
+
+    let errors = schema::validate(&json, format_version)?;
+
+    let file = args.file.display();
+
+    if errors.is_empty() {
+        let blue = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Blue)));
+        let bold = Style::new().bold();
+
+        println!(
+            "{blue}`{file}` is valid format version {bold}v{format_version}{bold:#} Rustdoc JSON{blue:#}"
+        );
+
+        return Ok(());
+    }
+
+    let red = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+
+    for error in &errors {
+        println!("{red}-{red:#} {error}");
+    }
+
+    Err(anyhow::anyhow!(
+        "`{file}` does not match the schema of format version v{format_version}, found {count} invalid values",
+        count = errors.len(),
+    ))
+}
+
+/// Prints the differences between the schemas of two format versions, the `schema-diff`
+/// subcommand.
+fn schema_diff(args: &SchemaDiffArgs) -> anyhow::Result<()> {
+    let diff = schema::diff(args.from.format_version(), args.to.format_version())?;
+
+    print!("{diff}");
 
     Ok(())
 }
@@ -0,0 +1,144 @@
+//! The migration manifest, a sidecar file that records the provenance of migrated JSON.
+//!
+//! A migrated `Crate` claims the new `format_version` as if Rustdoc had produced it natively. When
+//! `--manifest` is passed, a `<output>.migration.json` file is written next to the migrated JSON
+//! describing where it came from, so that downstream tools can warn when comparing migrated data.
+
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    filter::Filter,
+    reporter::{Caveat, Reporter},
+};
+
+/// The provenance of a migrated Rustdoc JSON file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The name of the tool that migrated the JSON, `migrate_rustdoc_json`.
+    pub tool: String,
+
+    /// The version of the tool that migrated the JSON.
+    pub tool_version: String,
+
+    /// The format version of the original JSON.
+    pub source_version: u32,
+
+    /// The hex-encoded SHA-256 hash of the original file, as it was stored on disk.
+    ///
+    /// If the original file was compressed, this is the hash of the compressed bytes.
+    pub source_sha256: String,
+
+    /// The format version the JSON was migrated to.
+    pub target_version: u32,
+
+    /// Each migration that was applied, in order.
+    pub hops: Vec<Hop>,
+
+    /// The filters that removed items from the migrated JSON, such as `--strip-private`.
+    pub filter: Filter,
+
+    /// The imperfect migrations that may require user intervention.
+    pub caveats: Vec<Caveat>,
+}
+
+/// A single migration from one format version to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hop {
+    pub from: u32,
+    pub to: u32,
+}
+
+impl Manifest {
+    /// The suffix appended to the output's file name to get the manifest's path.
+    pub const SUFFIX: &str = ".migration.json";
+
+    /// Creates the manifest for a finished migration that was pruned by `filter`, using the format
+    /// versions and caveats recorded by `reporter`.
+    ///
+    /// `source_sha256` is the [`hash_file()`] of the original file. It must be hashed before the
+    /// migrated JSON is written, as the output may overwrite the original file.
+    pub fn new(
+        source_sha256: String,
+        filter: &Filter,
+        reporter: &Reporter,
+    ) -> anyhow::Result<Self> {
+        let versions = reporter.versions();
+
+        let (&source_version, &target_version) = versions
+            .first()
+            .zip(versions.last())
+            .context("cannot create a manifest before migrating")?;
+
+        Ok(Self {
+            tool: env!("CARGO_PKG_NAME").to_owned(),
+            tool_version: env!("CARGO_PKG_VERSION").to_owned(),
+            source_version,
+            source_sha256,
+            target_version,
+            hops: versions
+                .windows(2)
+                .map(|pair| Hop {
+                    from: pair[0],
+                    to: pair[1],
+                })
+                .collect(),
+            filter: filter.clone(),
+            caveats: reporter.caveats().to_vec(),
+        })
+    }
+
+    /// Returns the path of the manifest for the migrated JSON at `output`.
+    ///
+    /// For example, the manifest of `migrated.json.gz` is `migrated.json.gz.migration.json`.
+    pub fn path_for(output: &Path) -> PathBuf {
+        let mut path = OsString::from(output);
+        path.push(Self::SUFFIX);
+
+        PathBuf::from(path)
+    }
+
+    /// Reads a manifest from `path`.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("could not read manifest: {}", path.display()))?;
+
+        serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("failed to deserialize manifest: {}", path.display()))
+    }
+
+    /// Writes this manifest to `path` as pretty-printed JSON.
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("could not create manifest: {}", path.display()))?;
+
+        let mut writer = BufWriter::new(file);
+
+        serde_json::to_writer_pretty(&mut writer, self)
+            .context("failed to serialize manifest to JSON")?;
+        writeln!(writer)
+            .and_then(|()| writer.flush())
+            .with_context(|| format!("could not write manifest: {}", path.display()))
+    }
+}
+
+/// Returns the hex-encoded SHA-256 hash of the file at `path`.
+pub fn hash_file(path: &Path) -> anyhow::Result<String> {
+    let mut file =
+        File::open(path).with_context(|| format!("could not hash file: {}", path.display()))?;
+
+    let mut hasher = Sha256::new();
+
+    io::copy(&mut file, &mut hasher)
+        .with_context(|| format!("could not hash file: {}", path.display()))?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
@@ -0,0 +1,212 @@
+//! Merging the Rustdoc JSON of several crates into a single workspace document.
+//!
+//! Rustdoc emits one JSON file per crate, and each numbers its `Id`s from scratch. A reference to
+//! an item in another crate goes through an entry in `Crate::paths`, whose `crate_id` points into
+//! `Crate::external_crates`.
+//!
+//! The workspace document contains every merged `Crate`, keyed by its name:
+//!
+//! ```json
+//! {
+//!     "format_version": 53,
+//!     "crates": {
+//!         "crate_a": { "root": 0, "index": {}, "paths": {}, ... },
+//!         "crate_b": { "root": 120, "index": {}, "paths": {}, ... }
+//!     }
+//! }
+//! ```
+//!
+//! The `Id`s of each crate are offset so that they are unique across the whole workspace. External
+//! `Id`s that refer to an item documented by another merged crate are replaced with that item's
+//! `Id`, so it can be looked up directly in the other crate's `index`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Context;
+use serde_json::{Map, Value, json};
+
+use crate::ids::{Id, LOCAL_CRATE_ID, map_ids, object, object_mut};
+
+/// A crate being merged, along with where its `Id`s start in the workspace.
+struct Member {
+    crate_: Value,
+    offset: Id,
+    /// The `Id`s of the items documented by this crate, keyed by their path and kind.
+    local_paths: HashMap<(Vec<String>, String), Id>,
+}
+
+/// Merges `crates` into a workspace document.
+///
+/// Every `Crate` must have the same format version. Crates are identified by the name of their
+/// root module, which must be unique.
+pub fn merge(crates: Vec<Value>) -> anyhow::Result<Value> {
+    let mut format_version = None;
+    let mut members: BTreeMap<String, Member> = BTreeMap::new();
+    let mut next_offset: Id = 0;
+
+    for crate_ in crates {
+        let version = crate_["format_version"]
+            .as_u64()
+            .context("`Crate::format_version` is not a number")?;
+
+        match format_version {
+            None => format_version = Some(version),
+            Some(expected) => anyhow::ensure!(
+                version == expected,
+                "cannot merge crates with different format versions, v{expected} and v{version}",
+            ),
+        }
+
+        let name = crate_name(&crate_)?;
+
+        let max_id = [object(&crate_, "index")?, object(&crate_, "paths")?]
+            .into_iter()
+            .flat_map(Map::keys)
+            .filter_map(|id| id.parse::<Id>().ok())
+            .max()
+            .unwrap_or(0);
+
+        let offset = next_offset;
+        next_offset = offset + max_id + 1;
+
+        anyhow::ensure!(
+            next_offset <= u64::from(u32::MAX) + 1,
+            "the merged crates have too many items for their `Id`s to fit in a `u32`",
+        );
+
+        let member = Member {
+            local_paths: local_paths(&crate_)?,
+            crate_,
+            offset,
+        };
+
+        anyhow::ensure!(
+            !members.contains_key(&name),
+            "cannot merge two crates named `{name}`",
+        );
+
+        members.insert(name, member);
+    }
+
+    // Find the `Id`s of external items that are documented by another merged crate, before any
+    // `Id`s are rewritten.
+    let resolved: Vec<HashMap<Id, Id>> = members
+        .values()
+        .map(|member| resolve_external_paths(&member.crate_, &members))
+        .collect::<anyhow::Result<_>>()?;
+
+    let mut crates = Map::new();
+
+    for ((name, member), resolved) in members.into_iter().zip(resolved) {
+        let Member {
+            mut crate_, offset, ..
+        } = member;
+
+        let mut map = |id: Id| resolved.get(&id).copied().unwrap_or(id + offset);
+
+        if let Some(root) = crate_["root"].as_u64() {
+            crate_["root"] = Value::from(map(root));
+        }
+
+        for key in ["index", "paths"] {
+            let entries = object_mut(&mut crate_, key)?;
+
+            *entries = std::mem::take(entries)
+                .into_iter()
+                .map(|(id, mut value)| {
+                    let id = match id.parse::<Id>() {
+                        Ok(id) => map(id).to_string(),
+                        Err(_) => id,
+                    };
+
+                    if key == "index" {
+                        map_ids(&mut value, &mut map);
+                    }
+
+                    (id, value)
+                })
+                .collect();
+        }
+
+        crates.insert(name, crate_);
+    }
+
+    Ok(json!({
+        "format_version": format_version.context("at least one crate is required to merge")?,
+        "crates": crates,
+    }))
+}
+
+/// Returns the name of a crate, which is the name of its root module.
+fn crate_name(crate_: &Value) -> anyhow::Result<String> {
+    let root = crate_["root"]
+        .as_u64()
+        .context("`Crate::root` is not an ID")?;
+
+    object(crate_, "index")?
+        .get(&root.to_string())
+        .and_then(|item| item["name"].as_str())
+        .map(str::to_owned)
+        .context("could not find the name of the crate's root module")
+}
+
+/// Returns the `Id`s of the items documented by a crate, keyed by their path and kind.
+fn local_paths(crate_: &Value) -> anyhow::Result<HashMap<(Vec<String>, String), Id>> {
+    let paths = object(crate_, "paths")?
+        .iter()
+        .filter(|(_, summary)| summary["crate_id"].as_u64() == Some(LOCAL_CRATE_ID))
+        .filter_map(|(id, summary)| Some((path_key(summary)?, id.parse().ok()?)))
+        .collect();
+
+    Ok(paths)
+}
+
+/// Returns the path and kind of an `ItemSummary`.
+fn path_key(summary: &Value) -> Option<(Vec<String>, String)> {
+    let path = summary["path"]
+        .as_array()?
+        .iter()
+        .map(|segment| segment.as_str().map(str::to_owned))
+        .collect::<Option<_>>()?;
+
+    let kind = summary["kind"].as_str()?.to_owned();
+
+    Some((path, kind))
+}
+
+/// Maps the `Id`s of a crate's external items to their `Id` in the workspace, for the items that
+/// are documented by another merged crate.
+fn resolve_external_paths(
+    crate_: &Value,
+    members: &BTreeMap<String, Member>,
+) -> anyhow::Result<HashMap<Id, Id>> {
+    let external_crates = object(crate_, "external_crates")?;
+
+    let mut resolved = HashMap::new();
+
+    for (id, summary) in object(crate_, "paths")? {
+        let Some(crate_id) = summary["crate_id"].as_u64() else {
+            continue;
+        };
+
+        if crate_id == LOCAL_CRATE_ID {
+            continue;
+        }
+
+        let Some(member) = external_crates
+            .get(&crate_id.to_string())
+            .and_then(|external| external["name"].as_str())
+            .and_then(|name| members.get(name))
+        else {
+            continue;
+        };
+
+        let target = path_key(summary).and_then(|key| member.local_paths.get(&key));
+
+        if let (Ok(id), Some(target)) = (id.parse::<Id>(), target) {
+            resolved.insert(id, member.offset + target);
+        }
+    }
+
+    Ok(resolved)
+}
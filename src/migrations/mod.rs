@@ -1,154 +1,798 @@
 //! All migrations currently implemented by this crate.
+//!
+//! Each format version is gated behind a Cargo feature of the same name, such as `v45`. The
+//! migration from one format version to the next is only compiled if both of their features are
+//! enabled. `min-vXX` features enable format version `vXX` and every version after it.
+
+use std::{
+    collections::HashSet,
+    io::{Read, Write},
+};
+
+use anyhow::Context;
+use serde_json::Value;
+
+use crate::{
+    filter,
+    fragment::FragmentKind,
+    ids::{self, Id},
+    output::OutputOptions,
+    reporter::{Phase, Reporter},
+    traits::{MigrateTo, SupportedCrate},
+    view::CrateView,
+};
+
+/// Implements [`MigrateTo`] from the `Crate` of the first format version to itself and every later
+/// one, then recurses on the later format versions.
+///
+/// Each implementation is only compiled if the features of every format version it migrates
+/// through are enabled, and chains their [`MigrateUp`] implementations.
+macro_rules! impl_migrate_to {
+    () => {};
+    (
+        $format_version:literal, $format_feature:literal, $rustdoc_types:ident;
+        $($up_version:literal, $up_feature:literal, $up_types:ident;)*
+    ) => {
+        #[cfg(feature = $format_feature)]
+        impl MigrateTo<$rustdoc_types::Crate> for $rustdoc_types::Crate {
+            fn migrate_to(self, _reporter: &mut Reporter) -> Self {
+                self
+            }
+        }
+
+        impl_migrate_to!(
+            @hops $rustdoc_types [$format_feature] []
+            $($up_version, $up_feature, $up_types;)*
+        );
+
+        impl_migrate_to!($($up_version, $up_feature, $up_types;)*);
+    };
+    (@hops $from:ident [$($feature:literal),*] [$($hop:literal),*]) => {};
+    (
+        @hops $from:ident [$($feature:literal),*] [$($hop:literal),*]
+        $up_version:literal, $up_feature:literal, $up_types:ident;
+        $($rest:tt)*
+    ) => {
+        #[cfg(all($(feature = $feature,)* feature = $up_feature))]
+        impl_migrate_to!(@impl $from => $up_types [$($hop,)* $up_version]);
+
+        impl_migrate_to!(
+            @hops $from [$($feature,)* $up_feature] [$($hop,)* $up_version]
+            $($rest)*
+        );
+    };
+    (@impl $from:ident => $to:ident [$($hop:literal),*]) => {
+        impl MigrateTo<$to::Crate> for $from::Crate {
+            fn migrate_to(self, reporter: &mut Reporter) -> $to::Crate {
+                let crate_ = self;
 
-use std::{any::Any, collections::HashMap, sync::LazyLock};
+                $(
+                    reporter.migrating_to($hop);
+                    reporter.migrating_items(crate_.index.len(), crate_.paths.len());
 
-use crate::reporter::Reporter;
+                    let crate_ = crate::traits::MigrateUp::migrate_up(crate_, reporter);
 
-type MigrateUpFn =
-    fn(crate_: Box<dyn Any>, reporter: &mut Reporter) -> anyhow::Result<Box<dyn Any>>;
-type DeserializeFn = fn(&str) -> anyhow::Result<Box<dyn Any>>;
-type SerializeFn = fn(crate_: Box<dyn Any>) -> anyhow::Result<String>;
+                    reporter.finished_migrating();
+                )*
 
-type MigrationMap = LazyLock<HashMap<u32, (MigrateUpFn, DeserializeFn, SerializeFn)>>;
+                crate_
+            }
+        }
+    };
+}
 
-/// A macro that generates the `mod v*;` statements and the [`MIGRATIONS`] map.
+/// A macro that generates the `mod v*;` statements, the [`VersionedCrate`] enum, the
+/// [`MigrateTo`] implementations, and the list of [`SUPPORTED_VERSIONS`].
 macro_rules! declare_migrations {
     {
-        #[first]
-        mod $first_name:ident ($first_version:expr);
-
-        $(mod $name:ident ($version:expr);)*
+        formats {
+            $($format_version:literal => $variant:ident, $format_feature:literal, $rustdoc_types:ident;)*
+        }
 
-        #[last]
-        mod $last_name:ident ($last_version:expr, $last_rustdoc_types:path);
+        migrations {
+            $(mod $name:ident ($current:ident => $up:ident, $feature:literal, $up_feature:literal);)*
+        }
 
-        static MIGRATIONS: MigrationMap = {};
+        pub enum VersionedCrate {}
 
-        pub const MINIMUM_VERSION: u32 = {};
-        pub const MAXIMUM_VERSION: u32 = {};
+        const ALL_VERSIONS: &[(u32, bool)] = {};
     } => {
-        mod $first_name;
-        $(mod $name;)*
+        #[cfg(not(any($(feature = $format_feature),*)))]
+        compile_error!("at least one format version feature, such as `min-v41`, must be enabled");
 
-        #[doc = concat!("**v", $last_version, " (de)serialization.**")]
+        $(
+            #[cfg(all(feature = $feature, feature = $up_feature))]
+            mod $name;
+        )*
+
+        /// A `Crate` of any format version supported with the currently enabled features.
         ///
-        /// Migrating past this version is not supported, so the `migrate_up()` function is a stub
-        /// that immediately errors.
-        mod $last_name {
-            use std::any::Any;
+        /// Each variant is gated behind the feature of its format version, such as `v45`.
+        #[derive(Clone, Debug, PartialEq, Eq)]
+        pub enum VersionedCrate {
+            $(
+                #[cfg(feature = $format_feature)]
+                #[doc = concat!("A format version v", $format_version, " `Crate`.")]
+                $variant($rustdoc_types::Crate),
+            )*
+        }
+
+        impl VersionedCrate {
+            /// Returns the format version of this `Crate`.
+            pub fn format_version(&self) -> u32 {
+                match self {
+                    $(
+                        #[cfg(feature = $format_feature)]
+                        Self::$variant(_) => $format_version,
+                    )*
+                }
+            }
+
+            /// Returns a read-only view of this `Crate`, which is the same for every format
+            /// version.
+            pub fn view(&self) -> &dyn CrateView {
+                match self {
+                    $(
+                        #[cfg(feature = $format_feature)]
+                        Self::$variant(crate_) => crate_,
+                    )*
+                }
+            }
+
+            /// Deserializes a `Crate` of the given format version from JSON.
+            ///
+            /// The `format_version` field of the JSON is not checked.
+            pub fn deserialize(format_version: u32, reader: impl Read) -> anyhow::Result<Self> {
+                let crate_ = match format_version {
+                    $(
+                        #[cfg(feature = $format_feature)]
+                        $format_version => Self::$variant(
+                            serde_json::from_reader(reader)
+                                .context("failed to deserialize `Crate` from JSON")?,
+                        ),
+                    )*
+                    _ => return Err(unsupported(format_version)),
+                };
 
-            use $last_rustdoc_types as current;
+                Ok(crate_)
+            }
+
+            /// Deserializes a `Crate` of the given format version from a JSON [`Value`].
+            ///
+            /// The `format_version` field of the JSON is not checked.
+            pub fn from_value(format_version: u32, value: Value) -> anyhow::Result<Self> {
+                let crate_ = match format_version {
+                    $(
+                        #[cfg(feature = $format_feature)]
+                        $format_version => Self::$variant(
+                            serde_json::from_value(value)
+                                .context("failed to deserialize `Crate` from JSON")?,
+                        ),
+                    )*
+                    _ => return Err(unsupported(format_version)),
+                };
 
-            crate::declare_serialize_deserialize!();
+                Ok(crate_)
+            }
 
-            /// Immediately returns an error that the given migration isn't yet supported.
-            pub fn migrate_up(_crate: Box<dyn Any>, _reporter: &mut $crate::reporter::Reporter) -> anyhow::Result<Box<dyn Any>> {
-                let current_version: u32 = $last_version;
+            /// Serializes this `Crate` to JSON, formatted according to `options`.
+            pub fn serialize(
+                &self,
+                options: &OutputOptions,
+                writer: impl Write,
+            ) -> anyhow::Result<()> {
+                match self {
+                    $(
+                        #[cfg(feature = $format_feature)]
+                        Self::$variant(crate_) => crate::output::to_writer(writer, crate_, options),
+                    )*
+                }
+                .context("failed to serialize `Crate` to JSON")
+            }
 
-                Err(anyhow::anyhow!(
-                    "migrating from format version v{} to format version v{} is not yet supported",
-                    current_version,
-                    current_version + 1,
-                ))
+            /// Migrates this `Crate` to the next format version.
+            ///
+            /// This errors if this is the newest supported format version, or if the next format
+            /// version's feature is disabled.
+            pub fn migrate_up(self, reporter: &mut Reporter) -> anyhow::Result<Self> {
+                // This is unused if no migrations are enabled, such as when only one format
+                // version is.
+                let _ = &reporter;
+
+                match self {
+                    $(
+                        #[cfg(all(feature = $feature, feature = $up_feature))]
+                        Self::$current(crate_) => {
+                            Ok(Self::$up($name::migrate_up(crate_, reporter)))
+                        }
+                    )*
+                    crate_ => Err(missing_migration(crate_.format_version())),
+                }
             }
         }
 
-        static MIGRATIONS: MigrationMap = LazyLock::new(|| {
-            let migrations = [
-                (
-                    $first_version,
-                    (
-                        $first_name::migrate_up as MigrateUpFn,
-                        $first_name::deserialize as DeserializeFn,
-                        $first_name::serialize as SerializeFn,
-                    ),
-                ),
+        $(
+            #[cfg(feature = $format_feature)]
+            impl From<$rustdoc_types::Crate> for VersionedCrate {
+                fn from(crate_: $rustdoc_types::Crate) -> Self {
+                    Self::$variant(crate_)
+                }
+            }
+
+            #[cfg(feature = $format_feature)]
+            impl TryFrom<VersionedCrate> for $rustdoc_types::Crate {
+                type Error = anyhow::Error;
+
+                fn try_from(crate_: VersionedCrate) -> anyhow::Result<Self> {
+                    match crate_ {
+                        VersionedCrate::$variant(crate_) => Ok(crate_),
+                        // Unreachable if this is the only enabled format version.
+                        #[allow(unreachable_patterns)]
+                        crate_ => Err(anyhow::anyhow!(
+                            "expected a format version v{} `Crate`, found v{}",
+                            $format_version,
+                            crate_.format_version(),
+                        )),
+                    }
+                }
+            }
+
+            #[cfg(feature = $format_feature)]
+            impl SupportedCrate for $rustdoc_types::Crate {
+                const FORMAT_VERSION: u32 = $format_version;
+            }
+        )*
+
+        impl_migrate_to!($($format_version, $format_feature, $rustdoc_types;)*);
+
+        /// Migrates a JSON fragment of format version `version` to the next format version.
+        fn migrate_fragment_up(
+            version: u32,
+            kind: FragmentKind,
+            fragment: Value,
+            reporter: &mut Reporter,
+        ) -> anyhow::Result<Value> {
+            // These are unused if no migrations are enabled, such as when only one format version
+            // is.
+            let _ = (&kind, &fragment, &reporter);
+
+            match version {
                 $(
-                    (
-                        $version,
-                        (
-                            $name::migrate_up as MigrateUpFn,
-                            $name::deserialize as DeserializeFn,
-                            $name::serialize as SerializeFn,
-                        ),
-                    ),
+                    #[cfg(all(feature = $feature, feature = $up_feature))]
+                    $name::FORMAT_VERSION => $name::migrate_fragment_up(kind, fragment, reporter),
                 )*
-                (
-                    $last_version,
-                    (
-                        $last_name::migrate_up as MigrateUpFn,
-                        $last_name::deserialize as DeserializeFn,
-                        $last_name::serialize as SerializeFn,
-                    ),
-                )
-            ];
-
-            HashMap::from(migrations)
-        });
-
-        pub const MINIMUM_VERSION: u32 = $first_version;
-        pub const MAXIMUM_VERSION: u32 = $last_version;
+                _ => Err(missing_migration(version)),
+            }
+        }
+
+        /// Errors if a JSON `Crate` cannot be deserialized as the given format version.
+        ///
+        /// The value of the `format_version` field is not checked.
+        fn check_crate(version: u32, crate_: &Value) -> anyhow::Result<()> {
+            match version {
+                $(
+                    #[cfg(feature = $format_feature)]
+                    $format_version => {
+                        <$rustdoc_types::Crate as serde::Deserialize>::deserialize(crate_)?;
+
+                        Ok(())
+                    }
+                )*
+                _ => Err(unsupported(version)),
+            }
+        }
+
+        /// Errors if a JSON `Item` cannot be deserialized as the given format version.
+        fn check_item(version: u32, item: &Value) -> anyhow::Result<()> {
+            match version {
+                $(
+                    #[cfg(feature = $format_feature)]
+                    $format_version => {
+                        <$rustdoc_types::Item as serde::Deserialize>::deserialize(item)?;
+
+                        Ok(())
+                    }
+                )*
+                _ => Err(unsupported(version)),
+            }
+        }
+
+        /// Errors if a JSON `ItemSummary` cannot be deserialized as the given format version.
+        fn check_item_summary(version: u32, summary: &Value) -> anyhow::Result<()> {
+            match version {
+                $(
+                    #[cfg(feature = $format_feature)]
+                    $format_version => {
+                        <$rustdoc_types::ItemSummary as serde::Deserialize>::deserialize(summary)?;
+
+                        Ok(())
+                    }
+                )*
+                _ => Err(unsupported(version)),
+            }
+        }
+
+        /// All format versions known by this crate, and whether their feature is enabled.
+        const ALL_VERSIONS: &[(u32, bool)] = &[$(($format_version, cfg!(feature = $format_feature))),*];
     };
 }
 
 declare_migrations! {
-    #[first]
-    mod v41 (41);
+    formats {
+        41 => V41, "v41", rustdoc_types_41;
+        42 => V42, "v42", rustdoc_types_42;
+        43 => V43, "v43", rustdoc_types_43;
+        44 => V44, "v44", rustdoc_types_44;
+        45 => V45, "v45", rustdoc_types_45;
+        46 => V46, "v46", rustdoc_types_46;
+        // v47 does not exist.
+        48 => V48, "v48", rustdoc_types_48;
+        49 => V49, "v49", rustdoc_types_49;
+        50 => V50, "v50", rustdoc_types_50;
+        51 => V51, "v51", rustdoc_types_51;
+        52 => V52, "v52", rustdoc_types_52;
+        53 => V53, "v53", rustdoc_types_53;
+    }
 
-    mod v42 (42);
-    mod v43 (43);
-    mod v44 (44);
-    mod v45 (45);
-    mod v46 (46);
-    mod v47 (47);
-    mod v48 (48);
-    mod v49 (49);
-    mod v50 (50);
-    mod v51 (51);
-    mod v52 (52);
+    migrations {
+        mod v41 (V41 => V42, "v41", "v42");
+        mod v42 (V42 => V43, "v42", "v43");
+        mod v43 (V43 => V44, "v43", "v44");
+        mod v44 (V44 => V45, "v44", "v45");
+        mod v45 (V45 => V46, "v45", "v46");
+        mod v46 (V46 => V48, "v46", "v48");
+        mod v48 (V48 => V49, "v48", "v49");
+        mod v49 (V49 => V50, "v49", "v50");
+        mod v50 (V50 => V51, "v50", "v51");
+        mod v51 (V51 => V52, "v51", "v52");
+        mod v52 (V52 => V53, "v52", "v53");
+    }
+
+    pub enum VersionedCrate { /* macro-generated */ }
+
+    const ALL_VERSIONS: &[(u32, bool)] = { /* macro-generated */ };
+}
+
+impl VersionedCrate {
+    /// Deserializes a `Crate` from JSON, as the format version in its `format_version` field.
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let version = crate::version::detect_version(json.as_bytes())?;
 
-    #[last]
-    mod v53 (53, rustdoc_types_53);
+        Self::deserialize(version, json.as_bytes())
+    }
 
-    static MIGRATIONS: MigrationMap = { /* macro-generated */ };
+    /// Migrates this `Crate` through every format version up to `to_version`.
+    pub fn migrate_to(self, to_version: u32, reporter: &mut Reporter) -> anyhow::Result<Self> {
+        ensure_not_downgrading(self.format_version(), to_version)?;
 
-    pub const MINIMUM_VERSION: u32 = { /* macro-generated */ };
-    pub const MAXIMUM_VERSION: u32 = { /* macro-generated */ };
+        let mut crate_ = self;
+
+        while crate_.format_version() < to_version {
+            // The current format version is known, so it must have a next version if it is older
+            // than `to_version`.
+            let up_version = next_version(crate_.format_version()).unwrap();
+
+            reporter.migrating_to(up_version);
+
+            crate_ = crate_.migrate_up(reporter)?;
+
+            reporter.finished_migrating();
+        }
+
+        Ok(crate_)
+    }
+}
+
+/// All format versions that can be (de)serialized with the currently enabled features, in
+/// ascending order.
+pub const SUPPORTED_VERSIONS: &[u32] = &supported_versions::<{ supported_versions_len() }>();
+
+/// The oldest format version supported with the currently enabled features.
+pub const MINIMUM_VERSION: u32 = SUPPORTED_VERSIONS[0];
+
+/// The newest format version supported with the currently enabled features.
+pub const MAXIMUM_VERSION: u32 = SUPPORTED_VERSIONS[SUPPORTED_VERSIONS.len() - 1];
+
+const fn supported_versions_len() -> usize {
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < ALL_VERSIONS.len() {
+        if ALL_VERSIONS[i].1 {
+            len += 1;
+        }
+
+        i += 1;
+    }
+
+    len
 }
 
+const fn supported_versions<const N: usize>() -> [u32; N] {
+    let mut versions = [0; N];
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < ALL_VERSIONS.len() {
+        let (version, enabled) = ALL_VERSIONS[i];
+
+        if enabled {
+            versions[len] = version;
+            len += 1;
+        }
+
+        i += 1;
+    }
+
+    versions
+}
+
+/// Returns the format version after `version`, if there is one.
+fn next_version(version: u32) -> Option<u32> {
+    let index = ALL_VERSIONS
+        .iter()
+        .position(|&(known, _)| known == version)?;
+
+    ALL_VERSIONS.get(index + 1).map(|&(next, _)| next)
+}
+
+/// Errors if the given format version cannot be (de)serialized with the enabled features.
+fn ensure_supported(version: u32) -> anyhow::Result<()> {
+    if SUPPORTED_VERSIONS.contains(&version) {
+        Ok(())
+    } else {
+        Err(unsupported(version))
+    }
+}
+
+/// Returns the error for a format version that cannot be (de)serialized with the enabled features.
+fn unsupported(version: u32) -> anyhow::Error {
+    if ALL_VERSIONS.iter().any(|&(known, _)| known == version) {
+        anyhow::anyhow!(
+            "support for format version v{version} is disabled, enable the `v{version}` feature of `migrate_rustdoc_json` to use it"
+        )
+    } else {
+        anyhow::anyhow!(
+            "format version v{version} is not supported, the supported format versions are {}",
+            crate::version::describe_supported_versions(),
+        )
+    }
+}
+
+/// Returns the error for a `Crate` that cannot be migrated to the next format version.
+fn missing_migration(version: u32) -> anyhow::Error {
+    match next_version(version) {
+        Some(up_version) => anyhow::anyhow!(
+            "migrating from format version v{version} to v{up_version} requires the `v{up_version}` feature of `migrate_rustdoc_json`"
+        ),
+        None => anyhow::anyhow!(
+            "format version v{version} is the newest format version, it cannot be migrated further"
+        ),
+    }
+}
+
+/// Migrates a Rustdoc JSON string to `to_version`, returning the migrated JSON.
 pub fn migrate_up(
     current: &str,
     to_version: u32,
+    options: &OutputOptions,
     reporter: &mut Reporter,
 ) -> anyhow::Result<String> {
-    let original_version = crate::version::detect_version(current)?;
+    let output = migrate_up_streaming(
+        || Ok(current.as_bytes()),
+        || Ok(Vec::new()),
+        None,
+        false,
+        to_version,
+        options,
+        reporter,
+    )?;
+
+    // `serde_json` only ever writes valid UTF-8.
+    Ok(String::from_utf8(output).unwrap())
+}
+
+/// Migrates a JSON fragment of a `Crate`, such as a single `Item` or `Type`, from `from_version`
+/// to `to_version`, returning the migrated JSON.
+///
+/// Fragments do not have a `format_version` field, so the format version they were produced by must
+/// be known. The caveats of the migration are recorded in `reporter`, so use a new [`Reporter`] for
+/// each fragment to find out which caveats apply to it.
+pub fn migrate_fragment(
+    kind: FragmentKind,
+    fragment: &str,
+    from_version: u32,
+    to_version: u32,
+    reporter: &mut Reporter,
+) -> anyhow::Result<String> {
+    ensure_supported(from_version)?;
+    ensure_supported(to_version)?;
+    ensure_not_downgrading(from_version, to_version)?;
+
+    let mut fragment: Value = serde_json::from_str(fragment).context("failed to parse JSON")?;
+
+    reporter.begin_migrating(from_version);
+
+    let mut version = from_version;
+
+    while version < to_version {
+        // `from_version` is supported, so it must have a next version if it is older than
+        // `to_version`.
+        let up_version = next_version(version).unwrap();
+
+        reporter.migrating_to(up_version);
+
+        fragment = migrate_fragment_up(version, kind, fragment, reporter)
+            .with_context(|| format!("failed to migrate `{kind:?}` to v{up_version}"))?;
+
+        reporter.finished_migrating();
+
+        version = up_version;
+    }
+
+    Ok(fragment.to_string())
+}
+
+/// Migrates Rustdoc JSON to `to_version`, streaming it from a reader and to a writer.
+///
+/// `open_input` is called twice: first to detect the format version of the JSON, then to
+/// deserialize it. This avoids holding the entire JSON in memory, at the cost of parsing it twice.
+///
+/// If `from_version` is specified, the JSON is deserialized as that format version and its
+/// `format_version` field is ignored. Otherwise, the format version is read from the JSON, falling
+/// back to [`compatible_versions()`] if the field is missing or wrong.
+///
+/// If `lenient` is true, items in `Crate::index` and entries in `Crate::paths` that cannot be
+/// deserialized as the format version, such as those with an enum variant added by a newer nightly
+/// toolchain, are dropped along with the references to them instead of failing the migration. A
+/// caveat is reported for each dropped item or entry. The format version is not guessed in this
+/// mode, so it must be specified or read from the `format_version` field.
+///
+/// `create_output` is only called once the migration has succeeded, right before the migrated
+/// JSON is written. The writer is returned so that the caller may flush or finish it.
+pub fn migrate_up_streaming<R: Read, W: Write>(
+    mut open_input: impl FnMut() -> anyhow::Result<R>,
+    create_output: impl FnOnce() -> anyhow::Result<W>,
+    from_version: Option<u32>,
+    lenient: bool,
+    to_version: u32,
+    options: &OutputOptions,
+    reporter: &mut Reporter,
+) -> anyhow::Result<W> {
+    ensure_supported(to_version)?;
+
+    let from_version = match from_version {
+        None if lenient => Some(crate::version::detect_version(open_input()?).context(
+            "`--lenient` cannot guess the format version, pass `--from-version` to choose it",
+        )?),
+        from_version => from_version,
+    };
+
+    let mut dropped = Vec::new();
+
+    let crate_ = match from_version {
+        Some(version) => {
+            ensure_supported(version)?;
+            ensure_not_downgrading(version, to_version)?;
+
+            // The `format_version` field may be missing or wrong, so it must be overwritten before
+            // the JSON can be deserialized.
+            let mut value = read_value(open_input()?, reporter)?;
+
+            if lenient {
+                dropped = drop_invalid_items(version, &mut value)?;
+            }
+
+            deserialize_value_as(version, value)?
+        }
+        None => detect_and_deserialize(&mut open_input, to_version, reporter)?,
+    };
+
+    reporter.begin_migrating(crate_.format_version());
+
+    for (id, caveat) in dropped {
+        reporter.item_caveat(id, caveat);
+    }
+
+    // Migrate the `Crate` through all versions between the input and the desired version.
+    let crate_ = crate_.migrate_to(to_version, reporter)?;
+
+    let mut output = create_output()?;
 
-    if original_version > to_version {
+    reporter.begin_phase(Phase::Serializing);
+
+    // Convert the `Crate` back to JSON.
+    crate_.serialize(options, reporter.track(&mut output))?;
+
+    reporter.end_phase();
+
+    Ok(output)
+}
+
+/// Returns the supported format versions that a `Crate` can be deserialized as, newest first.
+///
+/// The `Crate`'s `format_version` field is ignored, so this can be used to guess the format version
+/// of JSON where it is missing or wrong. Rustdoc's JSON does not reject unknown fields, so the JSON
+/// may be compatible with several format versions.
+pub fn compatible_versions(crate_: &Value) -> Vec<u32> {
+    let has_format_version = crate_["format_version"]
+        .as_u64()
+        .is_some_and(|version| u32::try_from(version).is_ok());
+
+    // `Crate`s without a valid `format_version` cannot be deserialized as any format version, so
+    // the field is replaced in a copy of the JSON. Its value is otherwise ignored.
+    let replaced;
+
+    let crate_ = match crate_.as_object() {
+        Some(object) if !has_format_version => {
+            let mut object = object.clone();
+            object.insert("format_version".to_owned(), Value::from(0));
+
+            replaced = Value::Object(object);
+            &replaced
+        }
+        _ => crate_,
+    };
+
+    SUPPORTED_VERSIONS
+        .iter()
+        .rev()
+        .copied()
+        .filter(|&version| check_crate(version, crate_).is_ok())
+        .collect()
+}
+
+/// Errors if `from_version` is newer than `to_version`.
+fn ensure_not_downgrading(from_version: u32, to_version: u32) -> anyhow::Result<()> {
+    if from_version > to_version {
         return Err(anyhow::anyhow!(
-            "`--input` format version {original_version} is greater than `--to-version` {to_version}"
+            "`--input` format version {from_version} is greater than `--to-version` {to_version}"
         )
         .context("downgrading to an older format version is not supported"));
     }
 
-    let deserialize = MIGRATIONS[&original_version].1;
+    Ok(())
+}
 
-    reporter.begin_migrating(original_version);
+/// Reads the `format_version` field of the JSON and deserializes it as that format version.
+///
+/// If the field is missing, names an unknown format version, or the JSON cannot be deserialized as
+/// it, the format version is guessed with [`compatible_versions()`] instead.
+fn detect_and_deserialize<R: Read>(
+    open_input: &mut impl FnMut() -> anyhow::Result<R>,
+    to_version: u32,
+    reporter: &mut Reporter,
+) -> anyhow::Result<VersionedCrate> {
+    let claimed = crate::version::detect_version(open_input()?);
+
+    let error = match claimed {
+        Ok(version) if ALL_VERSIONS.iter().any(|&(known, _)| known == version) => {
+            ensure_supported(version)?;
+            ensure_not_downgrading(version, to_version)?;
+
+            reporter.begin_phase(Phase::Deserializing);
 
-    // Convert the JSON string into a untyped `Crate`.
-    let mut crate_ = (deserialize)(current)?;
+            // Convert the JSON into a typed `Crate`.
+            let crate_ = VersionedCrate::deserialize(version, reporter.track(open_input()?));
 
-    for i in original_version..to_version {
-        let migrate_up = MIGRATIONS[&i].0;
+            reporter.end_phase();
+
+            match crate_ {
+                Ok(crate_) => return Ok(crate_),
+                Err(error) => error.context(format!(
+                    "the JSON claims to be format version v{version}, but could not be deserialized as it"
+                )),
+            }
+        }
+        Ok(version) => anyhow::anyhow!("the JSON claims to be unknown format version v{version}"),
+        Err(error) => error,
+    };
+
+    let value = read_value(open_input()?, reporter)?;
+
+    // Compatible versions newer than `to_version` are skipped, as they cannot be migrated.
+    let compatible: Vec<u32> = compatible_versions(&value)
+        .into_iter()
+        .filter(|&version| version <= to_version)
+        .collect();
+
+    let Some(&version) = compatible.first() else {
+        return Err(error.context(format!(
+            "could not detect the format version, the JSON is not compatible with any of {}",
+            crate::version::describe_supported_versions(),
+        )));
+    };
 
-        reporter.migrating_to(i + 1);
+    let compatible: Vec<String> = compatible.iter().map(|v| format!("v{v}")).collect();
 
-        // Migrate the untyped `Crate` through all versions between the input and the desired
-        // version.
-        crate_ = (migrate_up)(crate_, reporter)?;
+    reporter.warning(&format!(
+        "{error:#}. The JSON is compatible with {compatible}, so it will be migrated from v{version}. Pass `--from-version` to choose another.",
+        compatible = compatible.join(", "),
+    ));
+
+    deserialize_value_as(version, value)
+}
+
+/// Removes the items in `Crate::index` and the entries in `Crate::paths` that cannot be
+/// deserialized as the given format version, along with the references to them.
+///
+/// Returns the `Id` of each removed item or entry, and the caveat explaining why it was removed.
+fn drop_invalid_items(version: u32, crate_: &mut Value) -> anyhow::Result<Vec<(u32, String)>> {
+    let root = crate_["root"].as_u64();
+
+    let mut dropped = Vec::new();
+    let mut removed = HashSet::new();
+
+    for (id, item) in ids::object(crate_, "index")? {
+        // Keys that are not `Id`s are left for deserializing the `Crate` to reject.
+        let Ok(id) = id.parse::<u32>() else {
+            continue;
+        };
+
+        if let Err(error) = check_item(version, item) {
+            if root == Some(Id::from(id)) {
+                return Err(error.context(format!(
+                    "the crate root, item {id}, could not be deserialized as v{version}"
+                )));
+            }
+
+            removed.insert(Id::from(id));
+            dropped.push((
+                id,
+                format!(
+                    "dropped the item, as it could not be deserialized as v{version}: {error:#}"
+                ),
+            ));
+        }
     }
 
-    let serialize = MIGRATIONS[&to_version].2;
+    let paths = ids::object_mut(crate_, "paths")?;
+
+    paths.retain(|id, summary| {
+        let Ok(id) = id.parse::<u32>() else {
+            return true;
+        };
+
+        let Err(error) = check_item_summary(version, summary) else {
+            return true;
+        };
+
+        dropped.push((
+            id,
+            format!(
+                "dropped the `paths` entry, as it could not be deserialized as v{version}: {error:#}"
+            ),
+        ));
+
+        false
+    });
+
+    // `Use::id`s that pointed to the dropped `paths` entries are cleared here too.
+    filter::remove_items(crate_, &removed, false)?;
+
+    // Sort by `Id`, so that the caveats are reported in a stable order.
+    dropped.sort_by_key(|&(id, _)| id);
+
+    Ok(dropped)
+}
+
+/// Reads the whole JSON into a [`Value`].
+fn read_value(reader: impl Read, reporter: &mut Reporter) -> anyhow::Result<Value> {
+    reporter.begin_phase(Phase::Deserializing);
+
+    let value = serde_json::from_reader(reporter.track(reader)).context("failed to parse JSON");
+
+    reporter.end_phase();
+
+    value
+}
+
+/// Deserializes a `Crate` from a [`Value`] as the given format version, overwriting its
+/// `format_version` field.
+fn deserialize_value_as(version: u32, mut value: Value) -> anyhow::Result<VersionedCrate> {
+    if let Some(object) = value.as_object_mut() {
+        object.insert("format_version".to_owned(), Value::from(version));
+    }
 
-    // Convert the untyped `Crate` back to a JSON string.
-    (serialize)(crate_)
+    VersionedCrate::from_value(version, value)
 }
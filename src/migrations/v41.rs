@@ -4,62 +4,15 @@
 //!
 //! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md#v0380---2025-03-16>
 
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
 use rustdoc_types_41 as current;
-use rustdoc_types_42 as up;
 
-use crate::{declare_migrate_up, declare_serialize_deserialize};
+use crate::declare_migrate_up;
 
 declare_migrate_up!(41, 42);
-declare_serialize_deserialize!();
 
-crate::impl_unchanged_migrations! {
-    Crate,
-    AssocItemConstraint,
-    Constant,
-    Deprecation,
-    Discriminant,
-    DynTrait,
-    Enum,
-    ExternalCrate,
-    Function,
-    FunctionHeader,
-    FunctionPointer,
-    FunctionSignature,
-    GenericParamDef,
-    Generics,
-    Id,
-    Impl,
-    Item,
-    ItemSummary,
-    Module,
-    Path,
-    PolyTrait,
-    Primitive,
-    ProcMacro,
-    Span,
-    Static,
-    Struct,
-    Trait,
-    TraitAlias,
-    TypeAlias,
-    Union,
-    Use,
-    Variant,
-    Abi,
-    AssocItemConstraintKind,
-    GenericArg,
-    GenericArgs,
-    GenericBound,
-    GenericParamDefKind,
-    ItemEnum,
-    ItemKind,
-    MacroKind,
-    PreciseCapturingArg,
-    StructKind,
-    Term,
-    TraitBoundModifier,
-    Type,
-    VariantKind,
-    Visibility,
-    WherePredicate,
+impl_unchanged_migrations! {
+    current: rustdoc_types_41,
+    up: rustdoc_types_42,
+    manual: [],
 }
@@ -6,21 +6,23 @@
 //!
 //! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md#v0400---2025-04-19>
 
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
 use rustdoc_types_43 as current;
 use rustdoc_types_44 as up;
 
 use crate::{
-    declare_migrate_up, declare_serialize_deserialize, reporter::Reporter, traits::MigrateUp,
+    declare_migrate_up, primitives::migrate_entries_up, reporter::Reporter, traits::MigrateUp,
 };
 
 declare_migrate_up!(43, 44);
-declare_serialize_deserialize!();
 
 /// The `Crate::target` field was added, and defaults to be blank.
 impl MigrateUp for current::Crate {
     type Up = up::Crate;
 
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+
         reporter.caveat("`target.triple` and `target.target_features` are empty, as we cannot deduce their values".to_owned());
 
         let Self {
@@ -37,8 +39,8 @@ impl MigrateUp for current::Crate {
             root: root.migrate_up(reporter),
             crate_version: crate_version.migrate_up(reporter),
             includes_private: includes_private.migrate_up(reporter),
-            index: index.migrate_up(reporter),
-            paths: paths.migrate_up(reporter),
+            index: migrate_entries_up(index, reporter),
+            paths: migrate_entries_up(paths, reporter),
             external_crates: external_crates.migrate_up(reporter),
             target: up::Target {
                 // Currently we leave the target triple empty, as we can only guess if the JSON was
@@ -52,53 +54,8 @@ impl MigrateUp for current::Crate {
     }
 }
 
-crate::impl_unchanged_migrations! {
-    AssocItemConstraint,
-    Constant,
-    Deprecation,
-    Discriminant,
-    DynTrait,
-    Enum,
-    ExternalCrate,
-    Function,
-    FunctionHeader,
-    FunctionPointer,
-    FunctionSignature,
-    GenericParamDef,
-    Generics,
-    Id,
-    Impl,
-    Item,
-    ItemSummary,
-    Module,
-    Path,
-    PolyTrait,
-    Primitive,
-    ProcMacro,
-    Span,
-    Static,
-    Struct,
-    Trait,
-    TraitAlias,
-    TypeAlias,
-    Union,
-    Use,
-    Variant,
-    Abi,
-    AssocItemConstraintKind,
-    GenericArg,
-    GenericArgs@v42,
-    GenericBound,
-    GenericParamDefKind,
-    ItemEnum,
-    ItemKind,
-    MacroKind,
-    PreciseCapturingArg,
-    StructKind,
-    Term,
-    TraitBoundModifier,
-    Type,
-    VariantKind,
-    Visibility,
-    WherePredicate,
+impl_unchanged_migrations! {
+    current: rustdoc_types_43,
+    up: rustdoc_types_44,
+    manual: [Crate],
 }
@@ -5,21 +5,21 @@
 //!
 //! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md#v0410---2025-04-20>
 
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
 use rustdoc_types_44 as current;
 use rustdoc_types_45 as up;
 
-use crate::{
-    declare_migrate_up, declare_serialize_deserialize, reporter::Reporter, traits::MigrateUp,
-};
+use crate::{declare_migrate_up, reporter::Reporter, traits::MigrateUp};
 
 declare_migrate_up!(44, 45);
-declare_serialize_deserialize!();
 
 /// The column's index used to start at zero, now it starts at one.
 impl MigrateUp for current::Span {
     type Up = up::Span;
 
-    fn migrate_up(self, _reporter: &mut Reporter) -> Self::Up {
+    fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+
         let Self {
             filename,
             begin: (begin_line, begin_col),
@@ -34,55 +34,8 @@ impl MigrateUp for current::Span {
     }
 }
 
-crate::impl_unchanged_migrations! {
-    AssocItemConstraint,
-    Constant,
-    Crate@v44,
-    Deprecation,
-    Discriminant,
-    DynTrait,
-    Enum,
-    ExternalCrate,
-    Function,
-    FunctionHeader,
-    FunctionPointer,
-    FunctionSignature,
-    GenericParamDef,
-    Generics,
-    Id,
-    Impl,
-    Item,
-    ItemSummary,
-    Module,
-    Path,
-    PolyTrait,
-    Primitive,
-    ProcMacro,
-    Static,
-    Struct,
-    Target@v44,
-    TargetFeature@v44,
-    Trait,
-    TraitAlias,
-    TypeAlias,
-    Union,
-    Use,
-    Variant,
-    Abi,
-    AssocItemConstraintKind,
-    GenericArg,
-    GenericArgs@v42,
-    GenericBound,
-    GenericParamDefKind,
-    ItemEnum,
-    ItemKind,
-    MacroKind,
-    PreciseCapturingArg,
-    StructKind,
-    Term,
-    TraitBoundModifier,
-    Type,
-    VariantKind,
-    Visibility,
-    WherePredicate,
+impl_unchanged_migrations! {
+    current: rustdoc_types_44,
+    up: rustdoc_types_45,
+    manual: [Span],
 }
@@ -12,21 +12,20 @@
 //!
 //! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md#v0460---2025-06-03>
 
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
 use rustdoc_types_45 as current;
 use rustdoc_types_46 as up;
 
-use crate::{
-    declare_migrate_up, declare_serialize_deserialize, impl_unchanged_migrations,
-    reporter::Reporter,
-};
+use crate::{declare_migrate_up, primitives::migrate_entries_up, reporter::Reporter};
 
 declare_migrate_up!(45, 46);
-declare_serialize_deserialize!();
 
 impl crate::traits::MigrateUp for current::Crate {
     type Up = up::Crate;
 
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+
         reporter.caveat("`#[repr(transparent)]` types with only private fields may be missing `#[repr(transparent)]`".to_owned());
 
         let Self {
@@ -44,8 +43,8 @@ impl crate::traits::MigrateUp for current::Crate {
             root: root.migrate_up(reporter),
             crate_version: crate_version.migrate_up(reporter),
             includes_private: includes_private.migrate_up(reporter),
-            index: index.migrate_up(reporter),
-            paths: paths.migrate_up(reporter),
+            index: migrate_entries_up(index, reporter),
+            paths: migrate_entries_up(paths, reporter),
             external_crates: external_crates.migrate_up(reporter),
             target: target.migrate_up(reporter),
             format_version: format_version + 1,
@@ -54,54 +53,7 @@ impl crate::traits::MigrateUp for current::Crate {
 }
 
 impl_unchanged_migrations! {
-    AssocItemConstraint,
-    Constant,
-    Deprecation,
-    Discriminant,
-    DynTrait,
-    Enum,
-    ExternalCrate,
-    Function,
-    FunctionHeader,
-    FunctionPointer,
-    FunctionSignature,
-    GenericParamDef,
-    Generics,
-    Id,
-    Impl,
-    Item,
-    ItemSummary,
-    Module,
-    Path,
-    PolyTrait,
-    Primitive,
-    ProcMacro,
-    Span,
-    Static,
-    Struct,
-    Target@v44,
-    TargetFeature@v44,
-    Trait,
-    TraitAlias,
-    TypeAlias,
-    Union,
-    Use,
-    Variant,
-    Abi,
-    AssocItemConstraintKind,
-    GenericArg,
-    GenericArgs@v42,
-    GenericBound,
-    GenericParamDefKind,
-    ItemEnum,
-    ItemKind,
-    MacroKind,
-    PreciseCapturingArg,
-    StructKind,
-    Term,
-    TraitBoundModifier,
-    Type,
-    VariantKind,
-    Visibility,
-    WherePredicate,
+    current: rustdoc_types_45,
+    up: rustdoc_types_46,
+    manual: [Crate],
 }
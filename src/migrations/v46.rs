@@ -12,21 +12,23 @@
 //!
 //! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md#v0480---2025-06-19>
 
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
 use rustdoc_types_46 as current;
 use rustdoc_types_48 as up;
 
 use crate::{
-    declare_migrate_up, declare_serialize_deserialize, impl_unchanged_migrations,
-    reporter::Reporter, traits::MigrateUp,
+    attributes, declare_migrate_up, primitives::migrate_entries_up, reporter::Reporter,
+    traits::MigrateUp,
 };
 
 declare_migrate_up!(46, 48);
-declare_serialize_deserialize!();
 
 impl MigrateUp for current::Crate {
     type Up = up::Crate;
 
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+
         let Self {
             root,
             crate_version,
@@ -42,8 +44,8 @@ impl MigrateUp for current::Crate {
             root: root.migrate_up(reporter),
             crate_version: crate_version.migrate_up(reporter),
             includes_private: includes_private.migrate_up(reporter),
-            index: index.migrate_up(reporter),
-            paths: paths.migrate_up(reporter),
+            index: migrate_entries_up(index, reporter),
+            paths: migrate_entries_up(paths, reporter),
             external_crates: external_crates.migrate_up(reporter),
             target: target.migrate_up(reporter),
             // Bump the format version by 2, going from v46 to v48, since v47 does not exist.
@@ -56,6 +58,9 @@ impl MigrateUp for current::Item {
     type Up = up::Item;
 
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+        reporter.migrated_item();
+
         let Self {
             id,
             crate_id,
@@ -64,21 +69,12 @@ impl MigrateUp for current::Item {
             visibility,
             docs,
             links,
-            mut attrs,
+            attrs,
             deprecation,
             inner,
         } = self;
 
-        for attr in &mut attrs {
-            // If the attribute is some form of `#[inline]`, replace it with its structured form
-            // instead.
-            match attr.as_str() {
-                "#[inline]" => *attr = String::from("#[attr = Inline(Hint)]"),
-                "#[inline(always)]" => *attr = String::from("#[attr = Inline(Always)]"),
-                "#[inline(never)]" => *attr = String::from("#[attr = Inline(Never)]"),
-                _ => {}
-            }
-        }
+        let attrs = attributes::migrate_attrs(attrs, id.0, 46, 48, reporter);
 
         up::Item {
             id: id.migrate_up(reporter),
@@ -96,53 +92,7 @@ impl MigrateUp for current::Item {
 }
 
 impl_unchanged_migrations! {
-    AssocItemConstraint,
-    Constant,
-    Deprecation,
-    Discriminant,
-    DynTrait,
-    Enum,
-    ExternalCrate,
-    Function,
-    FunctionHeader,
-    FunctionPointer,
-    FunctionSignature,
-    GenericParamDef,
-    Generics,
-    Id,
-    Impl,
-    ItemSummary,
-    Module,
-    Path,
-    PolyTrait,
-    Primitive,
-    ProcMacro,
-    Span,
-    Static,
-    Struct,
-    Target@v44,
-    TargetFeature@v44,
-    Trait,
-    TraitAlias,
-    TypeAlias,
-    Union,
-    Use,
-    Variant,
-    Abi,
-    AssocItemConstraintKind,
-    GenericArg,
-    GenericArgs@v42,
-    GenericBound,
-    GenericParamDefKind,
-    ItemEnum,
-    ItemKind,
-    MacroKind,
-    PreciseCapturingArg,
-    StructKind,
-    Term,
-    TraitBoundModifier,
-    Type,
-    VariantKind,
-    Visibility,
-    WherePredicate,
+    current: rustdoc_types_46,
+    up: rustdoc_types_48,
+    manual: [Crate, Item],
 }
@@ -10,21 +10,21 @@
 //!
 //! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md#v0490---2025-06-23>
 
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
 use rustdoc_types_48 as current;
 use rustdoc_types_49 as up;
 
-use crate::{
-    declare_migrate_up, declare_serialize_deserialize, impl_unchanged_migrations,
-    reporter::Reporter, traits::MigrateUp,
-};
+use crate::{attributes, declare_migrate_up, reporter::Reporter, traits::MigrateUp};
 
 declare_migrate_up!(48, 49);
-declare_serialize_deserialize!();
 
 impl MigrateUp for current::Item {
     type Up = up::Item;
 
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+        reporter.migrated_item();
+
         let Self {
             id,
             crate_id,
@@ -33,26 +33,12 @@ impl MigrateUp for current::Item {
             visibility,
             docs,
             links,
-            mut attrs,
+            attrs,
             deprecation,
             inner,
         } = self;
 
-        for attr in &mut attrs {
-            if let Some(optimize) = attr
-                .strip_prefix("#[optimize(")
-                .and_then(|attr| attr.strip_suffix(")]"))
-            {
-                match optimize {
-                    "none" => *attr = String::from("#[attr = Optimize(DoNotOptimize)]"),
-                    "speed" => *attr = String::from("#[attr = Optimize(Speed)]"),
-                    "size" => *attr = String::from("#[attr = Optimize(Size)]"),
-                    _ => unreachable!(
-                        "attribute {attr} does not match expected `#[optimize(none|speed|size)]`",
-                    ),
-                }
-            }
-        }
+        let attrs = attributes::migrate_attrs(attrs, id.0, 48, 49, reporter);
 
         up::Item {
             id: id.migrate_up(reporter),
@@ -70,54 +56,7 @@ impl MigrateUp for current::Item {
 }
 
 impl_unchanged_migrations! {
-    Crate@v44,
-    AssocItemConstraint,
-    Constant,
-    Deprecation,
-    Discriminant,
-    DynTrait,
-    Enum,
-    ExternalCrate,
-    Function,
-    FunctionHeader,
-    FunctionPointer,
-    FunctionSignature,
-    GenericParamDef,
-    Generics,
-    Id,
-    Impl,
-    ItemSummary,
-    Module,
-    Path,
-    PolyTrait,
-    Primitive,
-    ProcMacro,
-    Span,
-    Static,
-    Struct,
-    Target@v44,
-    TargetFeature@v44,
-    Trait,
-    TraitAlias,
-    TypeAlias,
-    Union,
-    Use,
-    Variant,
-    Abi,
-    AssocItemConstraintKind,
-    GenericArg,
-    GenericArgs@v42,
-    GenericBound,
-    GenericParamDefKind,
-    ItemEnum,
-    ItemKind,
-    MacroKind,
-    PreciseCapturingArg,
-    StructKind,
-    Term,
-    TraitBoundModifier,
-    Type,
-    VariantKind,
-    Visibility,
-    WherePredicate,
+    current: rustdoc_types_48,
+    up: rustdoc_types_49,
+    manual: [Item],
 }
@@ -5,21 +5,20 @@
 //!
 //! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md#v0510---2025-06-23>
 
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
 use rustdoc_types_50 as current;
 use rustdoc_types_51 as up;
 
-use crate::{
-    declare_migrate_up, declare_serialize_deserialize, impl_unchanged_migrations,
-    reporter::Reporter, traits::MigrateUp,
-};
+use crate::{declare_migrate_up, reporter::Reporter, traits::MigrateUp};
 
 declare_migrate_up!(50, 51);
-declare_serialize_deserialize!();
 
 impl MigrateUp for current::AssocItemConstraint {
     type Up = up::AssocItemConstraint;
 
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+
         let Self {
             name,
             args,
@@ -38,6 +37,8 @@ impl MigrateUp for current::Type {
     type Up = up::Type;
 
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+
         match self {
             Self::Array { type_, len } => up::Type::Array {
                 type_: type_.migrate_up(reporter),
@@ -92,53 +93,7 @@ impl MigrateUp for current::Type {
 }
 
 impl_unchanged_migrations! {
-    Crate@v44,
-    Constant,
-    Deprecation,
-    Discriminant,
-    DynTrait,
-    Enum,
-    ExternalCrate,
-    Function,
-    FunctionHeader,
-    FunctionPointer,
-    FunctionSignature,
-    GenericParamDef,
-    Generics,
-    Id,
-    Impl,
-    Item,
-    ItemSummary,
-    Module,
-    Path,
-    PolyTrait,
-    Primitive,
-    ProcMacro,
-    Span,
-    Static,
-    Struct,
-    Target@v44,
-    TargetFeature@v44,
-    Trait,
-    TraitAlias,
-    TypeAlias,
-    Union,
-    Use,
-    Variant,
-    Abi,
-    AssocItemConstraintKind,
-    GenericArg,
-    GenericArgs@v42,
-    GenericBound,
-    GenericParamDefKind,
-    ItemEnum,
-    ItemKind,
-    MacroKind,
-    PreciseCapturingArg,
-    StructKind,
-    Term,
-    TraitBoundModifier,
-    VariantKind,
-    Visibility,
-    WherePredicate,
+    current: rustdoc_types_50,
+    up: rustdoc_types_51,
+    manual: [AssocItemConstraint, Type],
 }
@@ -9,21 +9,21 @@
 //!
 //! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md#v0520---2025-06-23>
 
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
 use rustdoc_types_51 as current;
 use rustdoc_types_52 as up;
 
-use crate::{
-    declare_migrate_up, declare_serialize_deserialize, impl_unchanged_migrations,
-    reporter::Reporter, traits::MigrateUp,
-};
+use crate::{attributes, declare_migrate_up, reporter::Reporter, traits::MigrateUp};
 
 declare_migrate_up!(51, 52);
-declare_serialize_deserialize!();
 
 impl MigrateUp for current::Item {
     type Up = up::Item;
 
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {
+        reporter.ran_override::<Self>();
+        reporter.migrated_item();
+
         let Self {
             id,
             crate_id,
@@ -32,24 +32,12 @@ impl MigrateUp for current::Item {
             visibility,
             docs,
             links,
-            mut attrs,
+            attrs,
             deprecation,
             inner,
         } = self;
 
-        for attr in &mut attrs {
-            if attr == "#[must_use]" {
-                *attr = String::from("#[attr = MustUse]");
-                continue;
-            }
-
-            if let Some(message) = attr
-                .strip_prefix("#[must_use = \"")
-                .and_then(|attr| attr.strip_suffix("\"]"))
-            {
-                *attr = format!("#[attr = MustUse {{reason: \"{message}\"}}]");
-            }
-        }
+        let attrs = attributes::migrate_attrs(attrs, id.0, 51, 52, reporter);
 
         up::Item {
             id: id.migrate_up(reporter),
@@ -67,54 +55,7 @@ impl MigrateUp for current::Item {
 }
 
 impl_unchanged_migrations! {
-    Crate@v44,
-    AssocItemConstraint,
-    Constant,
-    Deprecation,
-    Discriminant,
-    DynTrait,
-    Enum,
-    ExternalCrate,
-    Function,
-    FunctionHeader,
-    FunctionPointer,
-    FunctionSignature,
-    GenericParamDef,
-    Generics,
-    Id,
-    Impl,
-    ItemSummary,
-    Module,
-    Path,
-    PolyTrait,
-    Primitive,
-    ProcMacro,
-    Span,
-    Static,
-    Struct,
-    Target@v44,
-    TargetFeature@v44,
-    Trait,
-    TraitAlias,
-    TypeAlias,
-    Union,
-    Use,
-    Variant,
-    Abi,
-    AssocItemConstraintKind,
-    GenericArg,
-    GenericArgs@v42,
-    GenericBound,
-    GenericParamDefKind,
-    ItemEnum,
-    ItemKind,
-    MacroKind,
-    PreciseCapturingArg,
-    StructKind,
-    Term,
-    TraitBoundModifier,
-    Type,
-    VariantKind,
-    Visibility,
-    WherePredicate,
+    current: rustdoc_types_51,
+    up: rustdoc_types_52,
+    manual: [Item],
 }
@@ -0,0 +1,115 @@
+//! Options that control how the migrated JSON is filtered and formatted.
+
+use std::{cmp::Ordering, io::Write};
+
+use anyhow::Context;
+use serde::{Serialize, Serializer};
+use serde_json::{Value, ser::PrettyFormatter};
+
+use crate::filter::Filter;
+
+/// How the migrated JSON should be filtered and formatted.
+///
+/// The default options produce minified JSON with every item and keys in an unspecified order.
+#[derive(Clone, Debug, Default)]
+pub struct OutputOptions {
+    /// The number of spaces to indent the JSON with, or `None` to minify it.
+    pub indent: Option<usize>,
+
+    /// Whether object keys should be sorted.
+    ///
+    /// Numeric keys, such as the `Id`s in `Crate::index` and `Crate::paths`, are sorted by their
+    /// numeric value and placed before all other keys. Other keys are sorted lexicographically.
+    pub sort_keys: bool,
+
+    /// Which items should be removed from the migrated `Crate`.
+    pub filter: Filter,
+}
+
+impl OutputOptions {
+    /// The number of spaces used by `--pretty` when `--indent` is not specified.
+    pub const DEFAULT_INDENT: usize = 2;
+}
+
+/// Serializes `value` as JSON into `writer`, filtered and formatted according to `options`.
+///
+/// `value` must serialize to a `Crate` if [`OutputOptions::filter`] is not empty.
+pub fn to_writer<W: Write, T: Serialize + ?Sized>(
+    writer: W,
+    value: &T,
+    options: &OutputOptions,
+) -> anyhow::Result<()> {
+    if !options.sort_keys && options.filter.is_empty() {
+        return Ok(write(writer, value, options)?);
+    }
+
+    // Round-trip through `Value` so that items can be removed and the keys of all maps, including
+    // the `HashMap`s in `Crate`, can be sorted.
+    let mut value = serde_json::to_value(value)?;
+
+    options
+        .filter
+        .apply(&mut value)
+        .context("failed to filter the migrated `Crate`")?;
+
+    if options.sort_keys {
+        write(writer, &SortedValue(&value), options)?;
+    } else {
+        write(writer, &value, options)?;
+    }
+
+    Ok(())
+}
+
+fn write<W: Write, T: Serialize + ?Sized>(
+    writer: W,
+    value: &T,
+    options: &OutputOptions,
+) -> serde_json::Result<()> {
+    let Some(indent) = options.indent else {
+        return serde_json::to_writer(writer, value);
+    };
+
+    let indent = " ".repeat(indent);
+
+    let mut serializer = serde_json::Serializer::with_formatter(
+        writer,
+        PrettyFormatter::with_indent(indent.as_bytes()),
+    );
+
+    value.serialize(&mut serializer)
+}
+
+/// A [`Value`] that serializes the keys of its objects in the order described by
+/// [`OutputOptions::sort_keys`].
+struct SortedValue<'a>(&'a Value);
+
+impl Serialize for SortedValue<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Value::Array(values) => serializer.collect_seq(values.iter().map(SortedValue)),
+            Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+
+                entries.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+
+                serializer.collect_map(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key, SortedValue(value))),
+                )
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+/// Compares two object keys, sorting numeric keys by their value before all other keys.
+fn compare_keys(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
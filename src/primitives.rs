@@ -99,3 +99,99 @@ where
             .collect()
     }
 }
+
+/// Migrates the entries of `Crate::index` or `Crate::paths`.
+///
+/// These maps hold nearly all of a `Crate`, so with the `parallel` feature their entries are
+/// migrated across multiple threads. Other maps, such as `Item::links`, are small enough that
+/// migrating them in parallel would only add overhead.
+#[cfg(not(feature = "parallel"))]
+// Only migrations of `Crate` use this, which may all be disabled.
+#[cfg_attr(
+    not(any(
+        all(feature = "v41", feature = "v42"),
+        all(feature = "v42", feature = "v43"),
+        all(feature = "v43", feature = "v44"),
+        all(feature = "v44", feature = "v45"),
+        all(feature = "v45", feature = "v46"),
+        all(feature = "v46", feature = "v48"),
+        all(feature = "v48", feature = "v49"),
+        all(feature = "v49", feature = "v50"),
+        all(feature = "v50", feature = "v51"),
+        all(feature = "v51", feature = "v52"),
+        all(feature = "v52", feature = "v53"),
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn migrate_entries_up<K, V, S>(
+    map: HashMap<K, V, S>,
+    reporter: &mut Reporter,
+) -> HashMap<K::Up, V::Up, S>
+where
+    K: MigrateUp,
+    V: MigrateUp,
+    K::Up: Hash + Eq,
+    S: BuildHasher + Default,
+{
+    map.migrate_up(reporter)
+}
+
+/// Migrates the entries of `Crate::index` or `Crate::paths` across multiple threads.
+///
+/// Each entry is migrated with its own forked [`Reporter`]. The forks are then joined back in the
+/// map's iteration order, so caveats are reported in the same order as the sequential
+/// implementation.
+#[cfg(feature = "parallel")]
+// Only migrations of `Crate` use this, which may all be disabled.
+#[cfg_attr(
+    not(any(
+        all(feature = "v41", feature = "v42"),
+        all(feature = "v42", feature = "v43"),
+        all(feature = "v43", feature = "v44"),
+        all(feature = "v44", feature = "v45"),
+        all(feature = "v45", feature = "v46"),
+        all(feature = "v46", feature = "v48"),
+        all(feature = "v48", feature = "v49"),
+        all(feature = "v49", feature = "v50"),
+        all(feature = "v50", feature = "v51"),
+        all(feature = "v51", feature = "v52"),
+        all(feature = "v52", feature = "v53"),
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn migrate_entries_up<K, V, S>(
+    map: HashMap<K, V, S>,
+    reporter: &mut Reporter,
+) -> HashMap<K::Up, V::Up, S>
+where
+    K: MigrateUp + Hash + Eq + Send,
+    V: MigrateUp + Send,
+    K::Up: Hash + Eq + Send,
+    V::Up: Send,
+    S: BuildHasher + Default,
+{
+    use rayon::prelude::*;
+
+    let parent = &*reporter;
+
+    // `collect()` preserves the iteration order of the map, even though the entries are migrated
+    // out of order.
+    let migrated: Vec<_> = map
+        .into_par_iter()
+        .map(|(k, v)| {
+            let mut fork = parent.fork();
+            let entry = (k.migrate_up(&mut fork), v.migrate_up(&mut fork));
+
+            (entry, fork)
+        })
+        .collect();
+
+    let mut migrated_map = HashMap::with_capacity_and_hasher(migrated.len(), S::default());
+
+    for ((k, v), fork) in migrated {
+        reporter.join(fork);
+        migrated_map.insert(k, v);
+    }
+
+    migrated_map
+}
@@ -1,23 +1,94 @@
+mod progress;
+
+use std::{any, collections::BTreeMap, sync::Arc, time::Instant};
+
 use crate::args::Args;
 
 use anstream::eprintln;
 use anstyle::{AnsiColor, Color, Style};
+use serde::{Deserialize, Serialize};
+
+pub use self::progress::Phase;
+use self::progress::{Progress, Tracked};
+
+/// How much the [`Reporter`] prints.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Only errors are printed.
+    Quiet,
+    /// Progress, caveats, and errors are printed.
+    #[default]
+    Normal,
+    /// Everything in [`Verbosity::Normal`] is printed, along with timing, item counts, and which
+    /// manual migrations ran for each format version.
+    Verbose,
+}
 
 /// Struct for reporting information to the user.
 #[derive(Default)]
 pub struct Reporter {
+    verbosity: Verbosity,
     currently_migrating_to: u32,
+    /// The original format version, followed by every format version migrated to so far.
+    versions: Vec<u32>,
     caveats: Vec<Caveat>,
+    /// Statistics for the format version currently being migrated to, only collected when
+    /// [`Verbosity::Verbose`].
+    stats: HopStats,
+    /// The progress of the current [`Phase`], shared with forks so that they can advance it.
+    progress: Option<Arc<Progress>>,
 }
 
 impl Reporter {
+    /// Creates a new reporter with the given verbosity.
+    pub fn new(verbosity: Verbosity) -> Self {
+        Self {
+            verbosity,
+            ..Default::default()
+        }
+    }
+
     /// Configures a reporter to obey the CLI arguments.
-    pub fn configure(&mut self, _args: &Args) {
-        // TODO: interactive and color mode
+    pub fn configure(&mut self, args: &Args) {
+        self.verbosity = args.verbosity;
+
+        // `anstream` strips colors from everything printed with its `eprintln!()`, based on this
+        // global setting.
+        args.color.write_global();
+    }
+
+    /// Creates a reporter for migrating part of the `Crate` on another thread.
+    ///
+    /// The fork's caveats must be merged back into this reporter with [`Reporter::join()`].
+    pub fn fork(&self) -> Self {
+        Self {
+            verbosity: self.verbosity,
+            currently_migrating_to: self.currently_migrating_to,
+            versions: Vec::new(),
+            caveats: Vec::new(),
+            stats: HopStats::default(),
+            progress: self.progress.clone(),
+        }
+    }
+
+    /// Merges the caveats reported by a fork into this reporter.
+    pub fn join(&mut self, fork: Self) {
+        self.caveats.extend(fork.caveats);
+
+        for (name, count) in fork.stats.overrides {
+            *self.stats.overrides.entry(name).or_default() += count;
+        }
     }
 
     /// Tells the user that we have started migrating.
-    pub fn begin_migrating(&self, original_version: u32) {
+    pub fn begin_migrating(&mut self, original_version: u32) {
+        self.currently_migrating_to = original_version;
+        self.versions = vec![original_version];
+
+        if self.verbosity == Verbosity::Quiet {
+            return;
+        }
+
         eprintln!(
             "{blue}Migrating JSON with format version {bold}v{original_version}{bold:#}{blue:#}",
             blue = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Blue))),
@@ -28,6 +99,18 @@ impl Reporter {
     /// Tells the user we are migrating the JSON to a specific version.
     pub fn migrating_to(&mut self, version: u32) {
         self.currently_migrating_to = version;
+        self.versions.push(version);
+
+        if self.verbosity == Verbosity::Verbose {
+            self.stats = HopStats {
+                started: Some(Instant::now()),
+                ..Default::default()
+            };
+        }
+
+        if self.verbosity == Verbosity::Quiet {
+            return;
+        }
 
         eprintln!(
             "\t{dim}...to{dim:#} {blue}v{version}{blue:#}",
@@ -39,16 +122,151 @@ impl Reporter {
         );
     }
 
+    /// Records the number of items in `Crate::index` and `Crate::paths` being migrated, and starts
+    /// reporting the progress of migrating them.
+    pub fn migrating_items(&mut self, index: usize, paths: usize) {
+        self.stats.index = index;
+        self.stats.paths = paths;
+
+        self.begin_phase(Phase::Migrating { items: index });
+    }
+
+    /// Records that an `Item` was migrated, advancing the progress of [`Phase::Migrating`].
+    ///
+    /// This should be called by every `MigrateUp` implementation for `Item`.
+    pub fn migrated_item(&self) {
+        if let Some(progress) = &self.progress {
+            progress.advance(1);
+        }
+    }
+
+    /// Starts reporting the progress of `phase`, ending the previous phase if there was one.
+    ///
+    /// When stderr is a terminal, progress is drawn as a bar. Otherwise it is logged periodically.
+    /// Nothing is reported when [`Verbosity::Quiet`].
+    pub fn begin_phase(&mut self, phase: Phase) {
+        self.end_phase();
+
+        if self.verbosity == Verbosity::Quiet {
+            return;
+        }
+
+        self.progress = Some(Arc::new(Progress::start(phase)));
+    }
+
+    /// Stops reporting the progress of the current phase.
+    pub fn end_phase(&mut self) {
+        // Dropping the last reference to the progress stops drawing it.
+        self.progress = None;
+    }
+
+    /// Wraps a reader or writer so that the bytes passing through it advance the progress of the
+    /// current phase.
+    pub(crate) fn track<T>(&self, inner: T) -> Tracked<'_, T> {
+        Tracked::new(inner, self.progress.as_deref())
+    }
+
+    /// Records that a manual `MigrateUp` implementation ran for `T`.
+    ///
+    /// This should be called by every `MigrateUp` implementation that is not generated by
+    /// `impl_unchanged_migrations!`.
+    pub fn ran_override<T>(&mut self) {
+        if self.verbosity != Verbosity::Verbose {
+            return;
+        }
+
+        // `type_name()` returns the full path, such as `rustdoc_types::Item`.
+        let name = any::type_name::<T>().rsplit("::").next().unwrap();
+
+        *self.stats.overrides.entry(name).or_default() += 1;
+    }
+
+    /// Tells the user that we finished migrating to the current format version.
+    ///
+    /// This only prints when [`Verbosity::Verbose`].
+    pub fn finished_migrating(&mut self) {
+        self.end_phase();
+
+        if self.verbosity != Verbosity::Verbose {
+            return;
+        }
+
+        let HopStats {
+            started,
+            index,
+            paths,
+            overrides,
+        } = std::mem::take(&mut self.stats);
+
+        let dim = Style::new().dimmed();
+
+        eprintln!(
+            "\t\t{dim}took {elapsed:.2?}, migrated {index} items and {paths} paths{dim:#}",
+            elapsed = started.map(|started| started.elapsed()).unwrap_or_default(),
+        );
+
+        if !overrides.is_empty() {
+            let overrides: Vec<String> = overrides
+                .into_iter()
+                .map(|(name, count)| format!("{name} (x{count})"))
+                .collect();
+
+            eprintln!(
+                "\t\t{dim}manual migrations: {overrides}{dim:#}",
+                overrides = overrides.join(", "),
+            );
+        }
+    }
+
+    /// Warns the user about a problem with the input that the migration worked around, such as a
+    /// guessed format version.
+    pub fn warning(&self, message: &str) {
+        if self.verbosity == Verbosity::Quiet {
+            return;
+        }
+
+        eprintln!(
+            "{yellow}Warning{yellow:#}: {message}",
+            yellow = Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::Yellow)))
+                .bold(),
+        );
+    }
+
     /// Reports a caveat, that there was an imperfect migration that may require user intervention.
     pub fn caveat(&mut self, message: String) {
         self.caveats.push(Caveat {
             message,
             while_migrating_to: self.currently_migrating_to,
+            item: None,
         });
     }
 
+    /// Reports a caveat that only applies to the item with the given `Id`.
+    pub fn item_caveat(&mut self, item: u32, message: String) {
+        self.caveats.push(Caveat {
+            message,
+            while_migrating_to: self.currently_migrating_to,
+            item: Some(item),
+        });
+    }
+
+    /// Returns the original format version, followed by every format version migrated to so far.
+    pub fn versions(&self) -> &[u32] {
+        &self.versions
+    }
+
+    /// Returns the caveats reported so far.
+    pub fn caveats(&self) -> &[Caveat] {
+        &self.caveats
+    }
+
     /// Prints the final report after the migration has succeeded.
     pub fn print_success_report(&self) {
+        if self.verbosity == Verbosity::Quiet {
+            return;
+        }
+
         eprintln!(
             "{blue}Done!{blue:#} :D",
             blue = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Blue))),
@@ -64,9 +282,9 @@ impl Reporter {
 
             for caveat in self.caveats.iter() {
                 eprintln!(
-                    "\t- {msg} {dim}(while migrating to v{migrating_to}){dim:#}",
+                    "\t- {msg} {dim}({location}){dim:#}",
                     msg = caveat.message,
-                    migrating_to = caveat.while_migrating_to,
+                    location = caveat.location(),
                     dim = Style::new().dimmed().italic(),
                 );
             }
@@ -74,7 +292,12 @@ impl Reporter {
     }
 
     /// Prints the final report after the migration has failed.
-    pub fn print_error_report(&self, error: anyhow::Error) {
+    ///
+    /// Errors are always printed, even when [`Verbosity::Quiet`].
+    pub fn print_error_report(&mut self, error: anyhow::Error) {
+        // The failed phase may still be drawing its progress.
+        self.end_phase();
+
         let style = Style::new()
             .bold()
             .fg_color(Some(Color::Ansi(AnsiColor::BrightRed)));
@@ -84,7 +307,36 @@ impl Reporter {
 }
 
 /// A caveat, used to note imperfect migrations that may require user intervention.
-struct Caveat {
-    message: String,
-    while_migrating_to: u32,
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Caveat {
+    pub message: String,
+    /// The format version that was being migrated to when the caveat was reported, or the original
+    /// format version if it was reported before the first migration.
+    pub while_migrating_to: u32,
+    /// The `Id` of the item the caveat applies to, or `None` if it applies to the whole `Crate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item: Option<u32>,
+}
+
+impl Caveat {
+    /// Describes where the caveat was reported, such as `item 12, while migrating to v43`.
+    pub fn location(&self) -> String {
+        match self.item {
+            Some(item) => format!(
+                "item {item}, while migrating to v{}",
+                self.while_migrating_to
+            ),
+            None => format!("while migrating to v{}", self.while_migrating_to),
+        }
+    }
+}
+
+/// Statistics about migrating to a single format version.
+#[derive(Default)]
+struct HopStats {
+    started: Option<Instant>,
+    index: usize,
+    paths: usize,
+    /// The number of times each manual `MigrateUp` implementation ran, keyed by the type's name.
+    overrides: BTreeMap<&'static str, usize>,
 }
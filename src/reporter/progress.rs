@@ -0,0 +1,261 @@
+//! Progress reporting for long-running phases of a migration.
+//!
+//! When stderr is a terminal, progress is drawn as a bar that is redrawn in place and cleared once
+//! the phase finishes. Otherwise, a plain line is logged periodically, so that logs from CI do not
+//! fill up with redraws.
+
+use std::{
+    io::{self, IsTerminal, Read, Write},
+    sync::{
+        Arc, Condvar, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// How often the progress bar is redrawn when stderr is a terminal.
+const INTERACTIVE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often a progress line is logged when stderr is not a terminal.
+const PLAIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The width of the progress bar, in characters.
+const BAR_WIDTH: usize = 30;
+
+/// A phase of the migration that progress is reported for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Deserializing the input JSON. Progress is measured in bytes read.
+    Deserializing,
+    /// Migrating the items in `Crate::index` to the next format version. Progress is measured in
+    /// items, out of the given total.
+    Migrating { items: usize },
+    /// Serializing the migrated JSON. Progress is measured in bytes written.
+    Serializing,
+}
+
+impl Phase {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Deserializing => "deserializing",
+            Self::Migrating { .. } => "migrating",
+            Self::Serializing => "serializing",
+        }
+    }
+
+    fn total(self) -> Option<usize> {
+        match self {
+            Self::Migrating { items } => Some(items),
+            Self::Deserializing | Self::Serializing => None,
+        }
+    }
+}
+
+/// The progress of a single [`Phase`], drawn by a background thread until it is dropped.
+pub(crate) struct Progress {
+    shared: Arc<Shared>,
+    ticker: Option<JoinHandle<()>>,
+}
+
+/// The state shared between a [`Progress`] and its background thread.
+struct Shared {
+    phase: Phase,
+    position: AtomicUsize,
+    started: Instant,
+    interactive: bool,
+    finished: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Progress {
+    /// Starts reporting progress for `phase`.
+    pub fn start(phase: Phase) -> Self {
+        let shared = Arc::new(Shared {
+            phase,
+            position: AtomicUsize::new(0),
+            started: Instant::now(),
+            interactive: io::stderr().is_terminal(),
+            finished: Mutex::new(false),
+            condvar: Condvar::new(),
+        });
+
+        let ticker = {
+            let shared = Arc::clone(&shared);
+
+            thread::Builder::new()
+                .name("progress".to_owned())
+                .spawn(move || shared.tick_until_finished())
+                // Progress is purely informational, so failing to spawn the thread just hides it.
+                .ok()
+        };
+
+        Self { shared, ticker }
+    }
+
+    /// Advances the progress by `amount` items or bytes.
+    pub fn advance(&self, amount: usize) {
+        self.shared.position.fetch_add(amount, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        *self.shared.finished.lock().unwrap() = true;
+        self.shared.condvar.notify_all();
+
+        if let Some(ticker) = self.ticker.take() {
+            let _ = ticker.join();
+        }
+
+        if self.shared.interactive {
+            // Clear the progress bar, so that it doesn't linger after the phase has finished.
+            let _ = write!(io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+impl Shared {
+    fn tick_until_finished(&self) {
+        let interval = if self.interactive {
+            INTERACTIVE_INTERVAL
+        } else {
+            PLAIN_INTERVAL
+        };
+
+        let mut finished = self.finished.lock().unwrap();
+
+        loop {
+            (finished, _) = self
+                .condvar
+                .wait_timeout_while(finished, interval, |finished| !*finished)
+                .unwrap();
+
+            if *finished {
+                return;
+            }
+
+            if self.interactive {
+                self.draw_bar();
+            } else {
+                self.log_line();
+            }
+        }
+    }
+
+    /// Redraws the progress bar in place.
+    ///
+    /// This writes directly to stderr, rather than through `anstream`, because `anstream` would
+    /// strip the escape code that clears the line when colors are disabled.
+    fn draw_bar(&self) {
+        let position = self.position.load(Ordering::Relaxed);
+        let elapsed = self.started.elapsed().as_secs();
+
+        let line = match self.phase.total() {
+            Some(total) => {
+                let filled = (position * BAR_WIDTH)
+                    .checked_div(total)
+                    .unwrap_or(BAR_WIDTH);
+                let filled = filled.min(BAR_WIDTH);
+
+                format!(
+                    "\t\t{label} [{bar:<width$}] {position}/{total} items ({elapsed}s)",
+                    label = self.phase.label(),
+                    bar = "=".repeat(filled),
+                    width = BAR_WIDTH,
+                )
+            }
+            None => format!(
+                "\t\t{label}... {amount} ({elapsed}s)",
+                label = self.phase.label(),
+                amount = format_bytes(position),
+            ),
+        };
+
+        let _ = write!(io::stderr(), "\r\x1b[2K{line}");
+    }
+
+    /// Logs a line describing the current progress.
+    fn log_line(&self) {
+        let position = self.position.load(Ordering::Relaxed);
+        let elapsed = self.started.elapsed().as_secs();
+        let label = self.phase.label();
+
+        match self.phase.total() {
+            Some(total) => {
+                let percent = (position * 100).checked_div(total).unwrap_or(100);
+
+                anstream::eprintln!(
+                    "\t\t{label}: {position}/{total} items ({percent}%), {elapsed}s elapsed"
+                );
+            }
+            None => anstream::eprintln!(
+                "\t\t{label}: {amount}, {elapsed}s elapsed",
+                amount = format_bytes(position),
+            ),
+        }
+    }
+}
+
+/// Formats a number of bytes with a binary unit, such as `12.3 MiB`.
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut amount = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next_unit in &UNITS[1..] {
+        if amount < 1024.0 {
+            break;
+        }
+
+        amount /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{amount:.1} {unit}")
+}
+
+/// A reader or writer that advances a [`Progress`] by the number of bytes read or written.
+pub(crate) struct Tracked<'a, T> {
+    inner: T,
+    progress: Option<&'a Progress>,
+}
+
+impl<'a, T> Tracked<'a, T> {
+    pub fn new(inner: T, progress: Option<&'a Progress>) -> Self {
+        Self { inner, progress }
+    }
+
+    fn advance(&self, amount: usize) {
+        if let Some(progress) = self.progress {
+            progress.advance(amount);
+        }
+    }
+}
+
+impl<T: Read> Read for Tracked<'_, T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.advance(read);
+
+        Ok(read)
+    }
+}
+
+impl<T: Write> Write for Tracked<'_, T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.advance(written);
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
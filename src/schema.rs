@@ -0,0 +1,98 @@
+//! JSON Schemas of Rustdoc JSON, generated from the `rustdoc_types` of each format version.
+//!
+//! The schemas are generated at compile time by [`json_schema!`], which reads the source code of
+//! each enabled `rustdoc_types` dependency. Every public type is defined in `$defs` under its name,
+//! and the schema itself refers to `Crate`. Structs are objects, and enums are externally tagged,
+//! as they are (de)serialized by `serde`. The `Crate::format_version` field must equal the format
+//! version of the schema.
+//!
+//! Use [`validate()`] to check JSON against the schema of a format version, which reports the path
+//! to every invalid value rather than only the first error, and [`diff()`] to compare the schemas
+//! of two format versions.
+
+mod diff;
+mod validate;
+
+use anyhow::Context;
+use migrate_rustdoc_json_macros::json_schema;
+use serde_json::Value;
+
+pub use self::{
+    diff::{Change, Member, MemberDiff, SchemaDiff, TypeDiff},
+    validate::ValidationError,
+};
+
+/// Returns the JSON Schema of the given format version.
+pub fn schema(format_version: u32) -> anyhow::Result<Value> {
+    let source = schema_source(format_version).with_context(|| {
+        format!(
+            "format version v{format_version} is not supported, the supported format versions are {}",
+            crate::version::describe_supported_versions(),
+        )
+    })?;
+
+    // The schema is generated by `json_schema!`, which always produces valid JSON.
+    Ok(serde_json::from_str(source).unwrap())
+}
+
+/// Validates JSON against the schema of the given format version.
+///
+/// Every error is returned, ordered by where it was found. An empty list means the JSON is valid.
+pub fn validate(json: &Value, format_version: u32) -> anyhow::Result<Vec<ValidationError>> {
+    let schema = schema(format_version)?;
+
+    Ok(validate::validate(&schema, json))
+}
+
+/// Compares the schemas of two format versions, returning the types, fields, and enum variants
+/// that were added, removed, or changed from `from_version` to `to_version`.
+pub fn diff(from_version: u32, to_version: u32) -> anyhow::Result<SchemaDiff> {
+    let (from, to) = (schema(from_version)?, schema(to_version)?);
+
+    Ok(diff::diff(from_version, &from, to_version, &to))
+}
+
+/// Returns the name of the enum variant a branch of an enum's `oneOf` matches, if it is one.
+fn variant_name(branch: &Value) -> Option<&str> {
+    if let Some(name) = branch.get("const") {
+        return name.as_str();
+    }
+
+    match branch.get("required")?.as_array()?.as_slice() {
+        [name] if branch.get("additionalProperties") == Some(&Value::Bool(false)) => name.as_str(),
+        _ => None,
+    }
+}
+
+/// Declares [`schema_source()`] for every format version whose feature is enabled.
+macro_rules! schemas {
+    {
+        $($format_version:literal => $feature:literal, $rustdoc_types:ident;)*
+    } => {
+        /// Returns the JSON Schema of the given format version as a string, if it is supported.
+        fn schema_source(format_version: u32) -> Option<&'static str> {
+            match format_version {
+                $(
+                    #[cfg(feature = $feature)]
+                    $format_version => Some(json_schema!($rustdoc_types)),
+                )*
+                _ => None,
+            }
+        }
+    };
+}
+
+schemas! {
+    41 => "v41", rustdoc_types_41;
+    42 => "v42", rustdoc_types_42;
+    43 => "v43", rustdoc_types_43;
+    44 => "v44", rustdoc_types_44;
+    45 => "v45", rustdoc_types_45;
+    46 => "v46", rustdoc_types_46;
+    48 => "v48", rustdoc_types_48;
+    49 => "v49", rustdoc_types_49;
+    50 => "v50", rustdoc_types_50;
+    51 => "v51", rustdoc_types_51;
+    52 => "v52", rustdoc_types_52;
+    53 => "v53", rustdoc_types_53;
+}
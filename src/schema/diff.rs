@@ -0,0 +1,402 @@
+//! Comparing the schemas of two format versions.
+//!
+//! Types are compared by their shape, as described by the schema: the names and types of their
+//! fields, and the names and fields of their variants. Documentation is ignored, as is the value of
+//! `Crate::format_version`, which changes in every format version.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use super::variant_name;
+
+/// The differences between the schemas of two format versions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaDiff {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// The types that were added, removed, or changed, ordered by name.
+    pub types: Vec<TypeDiff>,
+}
+
+/// How a single type differs between two format versions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeDiff {
+    /// The name of the type, such as `ItemEnum`.
+    pub name: String,
+    /// Whether the type itself was added, removed, or changed kind, such as from a struct to an
+    /// enum. `None` if only its members changed.
+    pub change: Option<Change>,
+    /// The fields and variants that were added, removed, or changed.
+    pub members: Vec<MemberDiff>,
+}
+
+/// How a single field or variant differs between two format versions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemberDiff {
+    pub member: Member,
+    pub change: Change,
+}
+
+/// A field or variant of a type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Member {
+    Field(String),
+    Variant(String),
+    /// A field of a struct variant.
+    VariantField {
+        variant: String,
+        field: String,
+    },
+}
+
+/// A change to a type, field, or variant, along with a description of its shape, such as
+/// `Option<Type>` or `a struct`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    Added(String),
+    Removed(String),
+    Changed { from: String, to: String },
+}
+
+/// Compares the schemas of two format versions.
+pub fn diff(from_version: u32, from: &Value, to_version: u32, to: &Value) -> SchemaDiff {
+    let empty = Map::new();
+    let (from, to) = (defs(from, &empty), defs(to, &empty));
+
+    let mut names: Vec<&String> = from.keys().chain(to.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let types = names
+        .into_iter()
+        .filter_map(|name| {
+            let diff = match (from.get(name), to.get(name)) {
+                (Some(from), Some(to)) => diff_type(name, from, to),
+                (Some(from), None) => TypeDiff {
+                    name: name.clone(),
+                    change: Some(Change::Removed(describe_kind(from))),
+                    members: Vec::new(),
+                },
+                (None, Some(to)) => TypeDiff {
+                    name: name.clone(),
+                    change: Some(Change::Added(describe_kind(to))),
+                    members: Vec::new(),
+                },
+                (None, None) => unreachable!(),
+            };
+
+            (diff.change.is_some() || !diff.members.is_empty()).then_some(diff)
+        })
+        .collect();
+
+    SchemaDiff {
+        from_version,
+        to_version,
+        types,
+    }
+}
+
+/// Returns the definitions of a schema, or `empty` if it has none.
+fn defs<'a>(schema: &'a Value, empty: &'a Map<String, Value>) -> &'a Map<String, Value> {
+    schema
+        .get("$defs")
+        .and_then(Value::as_object)
+        .unwrap_or(empty)
+}
+
+fn diff_type(name: &str, from: &Value, to: &Value) -> TypeDiff {
+    let mut diff = TypeDiff {
+        name: name.to_owned(),
+        change: None,
+        members: Vec::new(),
+    };
+
+    match (variants(from), variants(to)) {
+        (Some(from), Some(to)) => diff.members = diff_variants(from, to),
+        (None, None) if is_struct(from) && is_struct(to) => {
+            diff.members = diff_fields(from, to)
+                .into_iter()
+                .map(|(field, change)| MemberDiff {
+                    member: Member::Field(field),
+                    change,
+                })
+                .collect();
+        }
+        _ => {
+            let (from, to) = (describe_kind(from), describe_kind(to));
+
+            if from != to {
+                diff.change = Some(Change::Changed { from, to });
+            }
+        }
+    }
+
+    diff
+}
+
+fn diff_variants(from: &[Value], to: &[Value]) -> Vec<MemberDiff> {
+    let mut members = Vec::new();
+
+    for from_variant in from {
+        let Some(name) = variant_name(from_variant) else {
+            continue;
+        };
+
+        let Some(to_variant) = find(to, name) else {
+            members.push(MemberDiff {
+                member: Member::Variant(name.to_owned()),
+                change: Change::Removed(describe_variant(from_variant)),
+            });
+
+            continue;
+        };
+
+        let (from_fields, to_fields) = (
+            &from_variant["properties"][name],
+            &to_variant["properties"][name],
+        );
+
+        // The fields of struct variants are compared individually.
+        if is_struct(from_fields) && is_struct(to_fields) {
+            members.extend(diff_fields(from_fields, to_fields).into_iter().map(
+                |(field, change)| MemberDiff {
+                    member: Member::VariantField {
+                        variant: name.to_owned(),
+                        field,
+                    },
+                    change,
+                },
+            ));
+
+            continue;
+        }
+
+        let (from, to) = (describe_variant(from_variant), describe_variant(to_variant));
+
+        if from != to {
+            members.push(MemberDiff {
+                member: Member::Variant(name.to_owned()),
+                change: Change::Changed { from, to },
+            });
+        }
+    }
+
+    for to_variant in to {
+        if let Some(name) = variant_name(to_variant).filter(|&name| find(from, name).is_none()) {
+            members.push(MemberDiff {
+                member: Member::Variant(name.to_owned()),
+                change: Change::Added(describe_variant(to_variant)),
+            });
+        }
+    }
+
+    members
+}
+
+/// Finds the variant with the given name.
+fn find<'a>(variants: &'a [Value], name: &str) -> Option<&'a Value> {
+    variants
+        .iter()
+        .find(|variant| variant_name(variant) == Some(name))
+}
+
+/// Compares the fields of two structs or struct variants, returning the name of each field that
+/// changed.
+fn diff_fields(from: &Value, to: &Value) -> Vec<(String, Change)> {
+    let (from_fields, to_fields) = (fields(from), fields(to));
+
+    let mut changes = Vec::new();
+
+    for (name, from_field) in &from_fields {
+        match to_fields.iter().find(|(to_name, _)| to_name == name) {
+            Some((_, to_field)) if to_field == from_field => {}
+            Some((_, to_field)) => changes.push((
+                name.clone(),
+                Change::Changed {
+                    from: from_field.clone(),
+                    to: to_field.clone(),
+                },
+            )),
+            None => changes.push((name.clone(), Change::Removed(from_field.clone()))),
+        }
+    }
+
+    for (name, to_field) in &to_fields {
+        if !from_fields.iter().any(|(from_name, _)| from_name == name) {
+            changes.push((name.clone(), Change::Added(to_field.clone())));
+        }
+    }
+
+    changes
+}
+
+/// Returns the name and description of each field of a struct or struct variant.
+fn fields(schema: &Value) -> Vec<(String, String)> {
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+
+    schema["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, field)| {
+            let mut description = describe(field);
+
+            // `Option` fields are never required, so only note fields that have another default.
+            if !required.contains(&name.as_str()) && !description.starts_with("Option<") {
+                description.push_str(" (optional)");
+            }
+
+            (name.clone(), description)
+        })
+        .collect()
+}
+
+fn variants(schema: &Value) -> Option<&[Value]> {
+    schema.get("oneOf")?.as_array().map(Vec::as_slice)
+}
+
+fn is_struct(schema: &Value) -> bool {
+    schema["type"] == "object" && schema.get("properties").is_some()
+}
+
+/// Describes what kind of type a definition is, such as `a struct`, `an enum`, or `u32` for a
+/// newtype.
+fn describe_kind(def: &Value) -> String {
+    if variants(def).is_some() {
+        "an enum".to_owned()
+    } else if is_struct(def) {
+        "a struct".to_owned()
+    } else {
+        describe(def)
+    }
+}
+
+/// Describes the fields of a variant, such as `unit`, `(Type)`, or `{ name: String }`.
+fn describe_variant(variant: &Value) -> String {
+    match variant_name(variant) {
+        Some(name) if variant.get("const").is_none() => {
+            let fields = &variant["properties"][name];
+
+            match fields.get("prefixItems").and_then(Value::as_array) {
+                // Tuple variants are already described in parentheses.
+                Some(_) => describe(fields),
+                None if is_struct(fields) => describe(fields),
+                None => format!("({})", describe(fields)),
+            }
+        }
+        _ => "unit".to_owned(),
+    }
+}
+
+/// Describes the type of a schema with Rust syntax, such as `Option<Vec<Type>>`.
+fn describe(schema: &Value) -> String {
+    if let Some(name) = schema["$ref"]
+        .as_str()
+        .and_then(|reference| reference.strip_prefix("#/$defs/"))
+    {
+        return name.to_owned();
+    }
+
+    if let Some([some, _]) = schema["anyOf"]
+        .as_array()
+        .map(Vec::as_slice)
+        .filter(|branches| matches!(branches, [_, none] if none["type"] == "null"))
+    {
+        return format!("Option<{}>", describe(some));
+    }
+
+    match schema["type"].as_str() {
+        Some("null") => "()".to_owned(),
+        Some("boolean") => "bool".to_owned(),
+        Some("string") => "String".to_owned(),
+        Some("number") => "f64".to_owned(),
+        Some("integer") => {
+            let is_unsigned = schema["minimum"] == 0;
+
+            match schema["maximum"].as_u64() {
+                Some(max) if is_unsigned && max == u64::from(u8::MAX) => "u8",
+                Some(max) if is_unsigned && max == u64::from(u16::MAX) => "u16",
+                Some(max) if is_unsigned && max == u64::from(u32::MAX) => "u32",
+                None if is_unsigned => "u64",
+                _ => "i64",
+            }
+            .to_owned()
+        }
+        Some("array") => match schema["prefixItems"].as_array() {
+            Some(items) => {
+                let items: Vec<String> = items.iter().map(describe).collect();
+
+                format!("({})", items.join(", "))
+            }
+            None => format!("Vec<{}>", describe(&schema["items"])),
+        },
+        Some("object") if is_struct(schema) => {
+            let fields: Vec<String> = fields(schema)
+                .into_iter()
+                .map(|(name, description)| format!("{name}: {description}"))
+                .collect();
+
+            format!("{{ {} }}", fields.join(", "))
+        }
+        Some("object") => format!("Map<String, {}>", describe(&schema["additionalProperties"])),
+        _ => "unknown".to_owned(),
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            from_version,
+            to_version,
+            types,
+        } = self;
+
+        if types.is_empty() {
+            return writeln!(
+                f,
+                "The schemas of v{from_version} and v{to_version} have the same types."
+            );
+        }
+
+        writeln!(f, "Changes from v{from_version} to v{to_version}:")?;
+
+        for diff in types {
+            let name = &diff.name;
+
+            match &diff.change {
+                Some(Change::Added(kind)) => writeln!(f, "+ `{name}`, {kind}")?,
+                Some(Change::Removed(kind)) => writeln!(f, "- `{name}`, {kind}")?,
+                Some(Change::Changed { from, to }) => {
+                    writeln!(f, "~ `{name}` changed from {from} to {to}")?
+                }
+                None => writeln!(f, "~ `{name}`")?,
+            }
+
+            for member in &diff.members {
+                let name = match &member.member {
+                    Member::Field(field) => format!("field `{field}`"),
+                    Member::Variant(variant) => format!("variant `{variant}`"),
+                    Member::VariantField { variant, field } => {
+                        format!("field `{field}` of variant `{variant}`")
+                    }
+                };
+
+                match &member.change {
+                    Change::Added(to) => writeln!(f, "    + {name}: `{to}`")?,
+                    Change::Removed(from) => writeln!(f, "    - {name}: `{from}`")?,
+                    Change::Changed { from, to } => {
+                        writeln!(f, "    ~ {name}: `{from}` to `{to}`")?
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
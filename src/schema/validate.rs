@@ -0,0 +1,366 @@
+//! A validator for the subset of JSON Schema used by the generated schemas.
+//!
+//! Only the keywords emitted by `json_schema!` are supported: `$ref`, `type`, `const`, `minimum`,
+//! `maximum`, `properties`, `required`, `additionalProperties`, `items`, `prefixItems`,
+//! `minItems`, `anyOf`, and `oneOf`. Other keywords, such as `title` and `description`, are
+//! ignored.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use super::variant_name;
+
+/// A value that does not match the schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The JSON Pointer to the invalid value, such as `/index/12/inner`, or an empty string for the
+    /// root of the JSON.
+    pub path: String,
+    /// Why the value is invalid.
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "at the root: {}", self.message)
+        } else {
+            write!(f, "at `{}`: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Validates `value` against `schema`, returning every error.
+pub fn validate(schema: &Value, value: &Value) -> Vec<ValidationError> {
+    let empty = Map::new();
+
+    let validator = Validator {
+        defs: schema
+            .get("$defs")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty),
+    };
+
+    let mut errors = Vec::new();
+
+    validator.validate(schema, value, &mut Vec::new(), &mut errors);
+
+    errors
+}
+
+struct Validator<'a> {
+    /// The definitions that `$ref`s may refer to.
+    defs: &'a Map<String, Value>,
+}
+
+impl Validator<'_> {
+    fn validate(
+        &self,
+        schema: &Value,
+        value: &Value,
+        path: &mut Vec<String>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match reference
+                .strip_prefix("#/$defs/")
+                .and_then(|name| self.defs.get(name))
+            {
+                Some(def) => self.validate(def, value, path, errors),
+                None => errors.push(error_at(
+                    path,
+                    format!("the schema refers to unknown definition `{reference}`"),
+                )),
+            }
+        }
+
+        let mut error = |message: String| errors.push(error_at(path, message));
+
+        if let Some(expected) = schema.get("const").filter(|expected| value != *expected) {
+            error(format!("expected `{expected}`, found {}", describe(value)));
+            return;
+        }
+
+        if let Some(expected) = schema
+            .get("type")
+            .and_then(Value::as_str)
+            .filter(|expected| !has_type(value, expected))
+        {
+            error(format!(
+                "expected {}, found {}",
+                describe_type(expected),
+                describe(value),
+            ));
+            return;
+        }
+
+        if let Some(number) = value.as_f64() {
+            if let Some(minimum) = schema
+                .get("minimum")
+                .and_then(Value::as_f64)
+                .filter(|&minimum| number < minimum)
+            {
+                error(format!("expected at least {minimum}, found {value}"));
+            }
+
+            if let Some(maximum) = schema
+                .get("maximum")
+                .and_then(Value::as_f64)
+                .filter(|&maximum| number > maximum)
+            {
+                error(format!("expected at most {maximum}, found {value}"));
+            }
+        }
+
+        match value {
+            Value::Object(object) => self.validate_object(schema, object, path, errors),
+            Value::Array(array) => self.validate_array(schema, array, path, errors),
+            _ => {}
+        }
+
+        let branches = schema
+            .get("oneOf")
+            .or_else(|| schema.get("anyOf"))
+            .and_then(Value::as_array);
+
+        if let Some(branches) = branches {
+            self.validate_branches(schema, branches, value, path, errors);
+        }
+    }
+
+    fn validate_object(
+        &self,
+        schema: &Value,
+        object: &Map<String, Value>,
+        path: &mut Vec<String>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let empty = Map::new();
+        let properties = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+
+        for field in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !object.contains_key(field) {
+                errors.push(error_at(path, format!("missing field `{field}`")));
+            }
+        }
+
+        let additional = schema.get("additionalProperties");
+
+        for (key, value) in object {
+            let schema = match (properties.get(key), additional) {
+                (Some(schema), _) => schema,
+                (None, Some(Value::Bool(false))) => {
+                    path.push(key.clone());
+                    errors.push(error_at(path, format!("unknown field `{key}`")));
+                    path.pop();
+
+                    continue;
+                }
+                (None, Some(schema)) if schema.is_object() => schema,
+                (None, _) => continue,
+            };
+
+            path.push(key.clone());
+            self.validate(schema, value, path, errors);
+            path.pop();
+        }
+    }
+
+    fn validate_array(
+        &self,
+        schema: &Value,
+        array: &[Value],
+        path: &mut Vec<String>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let prefix: &[Value] = schema
+            .get("prefixItems")
+            .and_then(Value::as_array)
+            .map_or(&[], Vec::as_slice);
+
+        let min_items = schema.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+
+        if (array.len() as u64) < min_items {
+            errors.push(error_at(
+                path,
+                format!(
+                    "expected at least {min_items} items, found {len}",
+                    len = array.len(),
+                ),
+            ));
+        }
+
+        let items = schema.get("items");
+
+        if items == Some(&Value::Bool(false)) && array.len() > prefix.len() {
+            errors.push(error_at(
+                path,
+                format!(
+                    "expected at most {max} items, found {len}",
+                    max = prefix.len(),
+                    len = array.len(),
+                ),
+            ));
+        }
+
+        for (i, value) in array.iter().enumerate() {
+            let schema = match prefix.get(i) {
+                Some(schema) => schema,
+                None => match items {
+                    Some(schema) if schema.is_object() => schema,
+                    _ => continue,
+                },
+            };
+
+            path.push(i.to_string());
+            self.validate(schema, value, path, errors);
+            path.pop();
+        }
+    }
+
+    /// Validates a value against `anyOf` or `oneOf`.
+    ///
+    /// Reporting that a value matches none of the branches is rarely helpful, so the errors of the
+    /// branch the value was most likely meant to match are reported instead, where possible.
+    fn validate_branches(
+        &self,
+        schema: &Value,
+        branches: &[Value],
+        value: &Value,
+        path: &mut Vec<String>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let mut branch_errors = Vec::with_capacity(branches.len());
+
+        for branch in branches {
+            let mut found = Vec::new();
+
+            self.validate(branch, value, path, &mut found);
+
+            if found.is_empty() {
+                return;
+            }
+
+            branch_errors.push(found);
+        }
+
+        // Enum variants are either a string or an object with a single key, the variant's name.
+        let variant = match value {
+            Value::String(name) => Some(name.as_str()),
+            Value::Object(object) if object.len() == 1 => object.keys().next().map(String::as_str),
+            _ => None,
+        };
+
+        let names: Vec<&str> = branches.iter().filter_map(variant_name).collect();
+
+        if let Some(variant) = variant.filter(|_| !names.is_empty()) {
+            if let Some(i) = branches
+                .iter()
+                .position(|branch| variant_name(branch) == Some(variant))
+            {
+                errors.append(&mut branch_errors[i]);
+            } else {
+                let names: Vec<String> = names.iter().map(|name| format!("`{name}`")).collect();
+
+                errors.push(error_at(
+                    path,
+                    format!(
+                        "unknown variant `{variant}`, expected one of {}",
+                        names.join(", "),
+                    ),
+                ));
+            }
+
+            return;
+        }
+
+        // `Option`s are a value or `null`, so report why a value that is not `null` is invalid.
+        let non_null: Vec<usize> = (0..branches.len())
+            .filter(|&i| value.is_null() || branches[i].get("type") != Some(&Value::from("null")))
+            .collect();
+
+        if let [i] = non_null[..] {
+            errors.append(&mut branch_errors[i]);
+
+            return;
+        }
+
+        let message = match schema.get("title").and_then(Value::as_str) {
+            Some(title) => format!("{} is not a valid `{title}`", capitalize(&describe(value))),
+            None => format!(
+                "{} does not match any of the allowed types",
+                capitalize(&describe(value))
+            ),
+        };
+
+        errors.push(error_at(path, message));
+    }
+}
+
+fn error_at(path: &[String], message: String) -> ValidationError {
+    // `~` and `/` are escaped in JSON Pointers.
+    let path = path
+        .iter()
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect();
+
+    ValidationError { path, message }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_u64() || value.is_i64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn describe_type(expected: &str) -> &str {
+    match expected {
+        "null" => "null",
+        "boolean" => "a boolean",
+        "integer" => "an integer",
+        "number" => "a number",
+        "string" => "a string",
+        "array" => "an array",
+        "object" => "an object",
+        other => other,
+    }
+}
+
+/// Describes a value for an error message, such as `the string "foo"` or `an object`.
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_owned(),
+        Value::Bool(value) => format!("the boolean `{value}`"),
+        Value::Number(value) if value.is_f64() => format!("the number `{value}`"),
+        Value::Number(value) => format!("the integer `{value}`"),
+        Value::String(value) => format!("the string {value:?}"),
+        Value::Array(_) => "an array".to_owned(),
+        Value::Object(_) => "an object".to_owned(),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
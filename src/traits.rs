@@ -1,6 +1,6 @@
-//! The [`MigrateUp`] trait.
+//! The [`MigrateUp`] and [`MigrateTo`] traits.
 
-use crate::reporter::Reporter;
+use crate::{migrations::VersionedCrate, reporter::Reporter};
 
 /// Migrates a `rustdoc_types` type from the current version to the next version.
 pub trait MigrateUp {
@@ -10,3 +10,33 @@ pub trait MigrateUp {
     /// Migrates `self` to the new version.
     fn migrate_up(self, reporter: &mut Reporter) -> Self::Up;
 }
+
+/// A `Crate` from one of the supported versions of `rustdoc_types`.
+///
+/// This is implemented for the `Crate` of every format version enabled by the current features.
+pub trait SupportedCrate: Into<VersionedCrate> + TryFrom<VersionedCrate> {
+    /// The format version of this `Crate`.
+    const FORMAT_VERSION: u32;
+}
+
+/// Migrates a `Crate` to a newer `Crate` of type `T`, through every format version in between.
+///
+/// This is implemented for every pair of `Crate`s whose format versions, and every format version
+/// between them, are enabled. Their [`MigrateUp`] implementations are chained, so no JSON is
+/// (de)serialized:
+///
+/// ```ignore
+/// use migrate_rustdoc_json::traits::MigrateTo;
+///
+/// reporter.begin_migrating(41);
+///
+/// let migrated: rustdoc_types_53::Crate = old.migrate_to(&mut reporter);
+/// ```
+///
+/// Like [`VersionedCrate::migrate_to()`], each hop is recorded in `reporter`, so call
+/// [`Reporter::begin_migrating()`] first. Migrating to an older `Crate`, or across a format version
+/// whose feature is disabled, fails to compile.
+pub trait MigrateTo<T> {
+    /// Migrates `self` to `T`.
+    fn migrate_to(self, reporter: &mut Reporter) -> T;
+}
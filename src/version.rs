@@ -3,20 +3,15 @@
 use std::{
     error::Error,
     fmt,
+    io::Read,
     num::{NonZero, ParseIntError},
-    ops::RangeInclusive,
     str::FromStr,
 };
 
 use anyhow::Context;
 use serde::Deserialize;
 
-/// A range of supported format versions.
-///
-/// `migrate_rustdoc_types` is able to (de)serialize all format versions in this range, and is able
-/// to migrate the lower bound all the way to the upper bound.
-const SUPPORTED_VERSIONS: RangeInclusive<u32> =
-    crate::migrations::MINIMUM_VERSION..=crate::migrations::MAXIMUM_VERSION;
+use crate::migrations::{MAXIMUM_VERSION, SUPPORTED_VERSIONS};
 
 /// Represents a format version supported by `migrate_rustdoc_types`.
 ///
@@ -32,7 +27,7 @@ pub enum ToVersion {
 impl ToVersion {
     pub fn format_version(&self) -> u32 {
         match self {
-            Self::Latest => *SUPPORTED_VERSIONS.end(),
+            Self::Latest => MAXIMUM_VERSION,
             Self::Specific(format_version) => format_version.get(),
         }
     }
@@ -69,7 +64,8 @@ impl fmt::Display for ParseToVersionError {
             Self::ParseInt(error) => error.fmt(f),
             Self::UnsupportedVersion(format_version) => write!(
                 f,
-                "format version {format_version} outside of supported range {SUPPORTED_VERSIONS:?}",
+                "format version {format_version} is not supported, the supported format versions are {}",
+                describe_supported_versions(),
             ),
         }
     }
@@ -84,8 +80,37 @@ impl Error for ParseToVersionError {
     }
 }
 
+/// Returns a human-readable list of [`SUPPORTED_VERSIONS`], such as `v41..=v46, v48..=v53`.
+///
+/// Consecutive format versions are collapsed into ranges.
+pub fn describe_supported_versions() -> String {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+
+    for &version in SUPPORTED_VERSIONS {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == version => *end = version,
+            _ => ranges.push((version, version)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("v{start}")
+            } else {
+                format!("v{start}..=v{end}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Detects the format version from Rustdoc's JSON output.
-pub fn detect_version(json: &str) -> anyhow::Result<u32> {
+///
+/// The JSON is streamed from `reader`, so the rest of the `Crate` is skipped without being stored
+/// in memory.
+pub fn detect_version(reader: impl Read) -> anyhow::Result<u32> {
     /// A simplified version of `rustdoc_types::Crate` that only has the `format_version` field.
     ///
     /// This type is intended to be independent of the format version so that it may deserialize
@@ -96,7 +121,7 @@ pub fn detect_version(json: &str) -> anyhow::Result<u32> {
     }
 
     let Crate { format_version } =
-        serde_json::from_str(json).context("failed to read format version of JSON")?;
+        serde_json::from_reader(reader).context("failed to read format version of JSON")?;
 
     Ok(format_version)
 }
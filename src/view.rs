@@ -0,0 +1,318 @@
+//! A read-only view of a `Crate` that is the same for every format version.
+//!
+//! Tools that only read names, paths, docs, and visibility can use [`CrateView`] and [`ItemView`]
+//! to accept JSON of any supported format version, without migrating it first. Values that changed
+//! representation between format versions are normalized:
+//!
+//! - The columns of [`Span`]s always begin at 1, as they do since v45.
+//! - [`ItemView::attrs()`] are always in the form used by the newest format version, such as
+//!   `#[attr = Inline(Hint)]` rather than `#[inline]`.
+//!
+//! Use [`VersionedCrate::view()`](crate::migrations::VersionedCrate::view) to view a `Crate` whose
+//! format version is only known at runtime.
+
+use std::path::Path;
+
+use crate::attributes;
+
+/// A read-only view of a `Crate` of any format version.
+pub trait CrateView {
+    /// The format version of the `Crate`.
+    fn format_version(&self) -> u32;
+
+    /// The version of the crate, if it was specified.
+    fn crate_version(&self) -> Option<&str>;
+
+    /// Whether items that are not public were documented.
+    fn includes_private(&self) -> bool;
+
+    /// The `Id` of the crate's root module.
+    fn root(&self) -> u32;
+
+    /// Returns the item in `Crate::index` with the given `Id`.
+    fn item(&self, id: u32) -> Option<&dyn ItemView>;
+
+    /// Returns every item in `Crate::index`, in an unspecified order.
+    fn items(&self) -> Box<dyn Iterator<Item = &dyn ItemView> + '_>;
+
+    /// Returns the entry in `Crate::paths` with the given `Id`.
+    fn path(&self, id: u32) -> Option<PathView<'_>>;
+}
+
+/// A read-only view of an `Item` of any format version.
+pub trait ItemView {
+    /// The `Id` of the item.
+    fn id(&self) -> u32;
+
+    /// The `Id` of the crate the item is from, where 0 is the local crate.
+    fn crate_id(&self) -> u32;
+
+    /// The name of the item, if it has one.
+    fn name(&self) -> Option<&str>;
+
+    /// The documentation of the item, if it has any.
+    fn docs(&self) -> Option<&str>;
+
+    /// The kind of the item.
+    fn kind(&self) -> ItemKind;
+
+    /// The visibility of the item.
+    fn visibility(&self) -> Visibility<'_>;
+
+    /// Where the item is defined, if it is known.
+    fn span(&self) -> Option<Span<'_>>;
+
+    /// The attributes of the item, in the form used by the newest format version.
+    fn attrs(&self) -> Vec<String>;
+}
+
+/// An entry in `Crate::paths`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathView<'a> {
+    /// The `Id` of the crate the item is from, where 0 is the local crate.
+    pub crate_id: u32,
+    /// The full path of the item, such as `["std", "vec", "Vec"]`.
+    pub path: &'a [String],
+    /// The kind of the item.
+    pub kind: ItemKind,
+}
+
+/// The kind of an item, the same in every format version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    Module,
+    ExternCrate,
+    Use,
+    Struct,
+    StructField,
+    Union,
+    Enum,
+    Variant,
+    Function,
+    TypeAlias,
+    Constant,
+    Trait,
+    TraitAlias,
+    Impl,
+    Static,
+    ExternType,
+    Macro,
+    ProcAttribute,
+    ProcDerive,
+    AssocConst,
+    AssocType,
+    Primitive,
+    Keyword,
+}
+
+/// The visibility of an item, the same in every format version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visibility<'a> {
+    /// `pub`.
+    Public,
+    /// The visibility is inherited from the parent, such as for enum variants and trait items.
+    Default,
+    /// `pub(crate)`.
+    Crate,
+    /// `pub(in path)`, where `parent` is the `Id` of the module named by `path`.
+    Restricted { parent: u32, path: &'a str },
+}
+
+/// Where an item is defined, the same in every format version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    /// The path to the source file, relative to where Rustdoc was run.
+    pub filename: &'a Path,
+    /// The line and column of the first character of the item, both beginning at 1.
+    pub begin: (usize, usize),
+    /// The line and column of the last character of the item, both beginning at 1.
+    pub end: (usize, usize),
+}
+
+/// Returns a column that begins at 1, given a column from a `Span` of `format_version`.
+fn normalize_column(format_version: u32, column: usize) -> usize {
+    // Columns began at 0 before v45.
+    if format_version < 45 {
+        column + 1
+    } else {
+        column
+    }
+}
+
+/// Implements [`CrateView`] and [`ItemView`] for the `Crate` and `Item` of each format version.
+macro_rules! impl_views {
+    {
+        $($format_version:literal => $feature:literal, $rustdoc_types:ident;)*
+    } => {
+        $(
+            #[cfg(feature = $feature)]
+            impl CrateView for $rustdoc_types::Crate {
+                fn format_version(&self) -> u32 {
+                    $format_version
+                }
+
+                fn crate_version(&self) -> Option<&str> {
+                    self.crate_version.as_deref()
+                }
+
+                fn includes_private(&self) -> bool {
+                    self.includes_private
+                }
+
+                fn root(&self) -> u32 {
+                    self.root.0
+                }
+
+                fn item(&self, id: u32) -> Option<&dyn ItemView> {
+                    self.index
+                        .get(&$rustdoc_types::Id(id))
+                        .map(|item| item as &dyn ItemView)
+                }
+
+                fn items(&self) -> Box<dyn Iterator<Item = &dyn ItemView> + '_> {
+                    Box::new(self.index.values().map(|item| item as &dyn ItemView))
+                }
+
+                fn path(&self, id: u32) -> Option<PathView<'_>> {
+                    use $rustdoc_types::ItemKind as Kind;
+
+                    let summary = self.paths.get(&$rustdoc_types::Id(id))?;
+
+                    let kind = match summary.kind {
+                        Kind::Module => ItemKind::Module,
+                        Kind::ExternCrate => ItemKind::ExternCrate,
+                        Kind::Use => ItemKind::Use,
+                        Kind::Struct => ItemKind::Struct,
+                        Kind::StructField => ItemKind::StructField,
+                        Kind::Union => ItemKind::Union,
+                        Kind::Enum => ItemKind::Enum,
+                        Kind::Variant => ItemKind::Variant,
+                        Kind::Function => ItemKind::Function,
+                        Kind::TypeAlias => ItemKind::TypeAlias,
+                        Kind::Constant => ItemKind::Constant,
+                        Kind::Trait => ItemKind::Trait,
+                        Kind::TraitAlias => ItemKind::TraitAlias,
+                        Kind::Impl => ItemKind::Impl,
+                        Kind::Static => ItemKind::Static,
+                        Kind::ExternType => ItemKind::ExternType,
+                        Kind::Macro => ItemKind::Macro,
+                        Kind::ProcAttribute => ItemKind::ProcAttribute,
+                        Kind::ProcDerive => ItemKind::ProcDerive,
+                        Kind::AssocConst => ItemKind::AssocConst,
+                        Kind::AssocType => ItemKind::AssocType,
+                        Kind::Primitive => ItemKind::Primitive,
+                        Kind::Keyword => ItemKind::Keyword,
+                    };
+
+                    Some(PathView {
+                        crate_id: summary.crate_id,
+                        path: &summary.path,
+                        kind,
+                    })
+                }
+            }
+
+            #[cfg(feature = $feature)]
+            impl ItemView for $rustdoc_types::Item {
+                fn id(&self) -> u32 {
+                    self.id.0
+                }
+
+                fn crate_id(&self) -> u32 {
+                    self.crate_id
+                }
+
+                fn name(&self) -> Option<&str> {
+                    self.name.as_deref()
+                }
+
+                fn docs(&self) -> Option<&str> {
+                    self.docs.as_deref()
+                }
+
+                fn kind(&self) -> ItemKind {
+                    use $rustdoc_types::{ItemEnum, MacroKind};
+
+                    // Braces are used for every variant, as they match unit, tuple, and struct
+                    // variants alike.
+                    match &self.inner {
+                        ItemEnum::Module { .. } => ItemKind::Module,
+                        ItemEnum::ExternCrate { .. } => ItemKind::ExternCrate,
+                        ItemEnum::Use { .. } => ItemKind::Use,
+                        ItemEnum::Union { .. } => ItemKind::Union,
+                        ItemEnum::Struct { .. } => ItemKind::Struct,
+                        ItemEnum::StructField { .. } => ItemKind::StructField,
+                        ItemEnum::Enum { .. } => ItemKind::Enum,
+                        ItemEnum::Variant { .. } => ItemKind::Variant,
+                        ItemEnum::Function { .. } => ItemKind::Function,
+                        ItemEnum::TraitAlias { .. } => ItemKind::TraitAlias,
+                        ItemEnum::Trait { .. } => ItemKind::Trait,
+                        ItemEnum::Impl { .. } => ItemKind::Impl,
+                        ItemEnum::TypeAlias { .. } => ItemKind::TypeAlias,
+                        ItemEnum::Constant { .. } => ItemKind::Constant,
+                        ItemEnum::Static { .. } => ItemKind::Static,
+                        ItemEnum::ExternType { .. } => ItemKind::ExternType,
+                        ItemEnum::Macro { .. } => ItemKind::Macro,
+                        ItemEnum::ProcMacro(proc_macro) => match proc_macro.kind {
+                            MacroKind::Bang => ItemKind::Macro,
+                            MacroKind::Attr => ItemKind::ProcAttribute,
+                            MacroKind::Derive => ItemKind::ProcDerive,
+                        },
+                        ItemEnum::Primitive { .. } => ItemKind::Primitive,
+                        ItemEnum::AssocConst { .. } => ItemKind::AssocConst,
+                        ItemEnum::AssocType { .. } => ItemKind::AssocType,
+                    }
+                }
+
+                fn visibility(&self) -> Visibility<'_> {
+                    use $rustdoc_types::Visibility as Vis;
+
+                    match &self.visibility {
+                        Vis::Public => Visibility::Public,
+                        Vis::Default => Visibility::Default,
+                        Vis::Crate => Visibility::Crate,
+                        Vis::Restricted { parent, path } => Visibility::Restricted {
+                            parent: parent.0,
+                            path,
+                        },
+                    }
+                }
+
+                fn span(&self) -> Option<Span<'_>> {
+                    let span = self.span.as_ref()?;
+
+                    Some(Span {
+                        filename: &span.filename,
+                        begin: (
+                            span.begin.0,
+                            normalize_column($format_version, span.begin.1),
+                        ),
+                        end: (span.end.0, normalize_column($format_version, span.end.1)),
+                    })
+                }
+
+                fn attrs(&self) -> Vec<String> {
+                    self.attrs
+                        .iter()
+                        .filter_map(|attr| attributes::normalize(attr))
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_views! {
+    41 => "v41", rustdoc_types_41;
+    42 => "v42", rustdoc_types_42;
+    43 => "v43", rustdoc_types_43;
+    44 => "v44", rustdoc_types_44;
+    45 => "v45", rustdoc_types_45;
+    46 => "v46", rustdoc_types_46;
+    48 => "v48", rustdoc_types_48;
+    49 => "v49", rustdoc_types_49;
+    50 => "v50", rustdoc_types_50;
+    51 => "v51", rustdoc_types_51;
+    52 => "v52", rustdoc_types_52;
+    53 => "v53", rustdoc_types_53;
+}
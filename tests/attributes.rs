@@ -0,0 +1,207 @@
+//! Tests that attributes are parsed from, and rendered to, the form of every format version.
+
+use migrate_rustdoc_json::attributes::{Attribute, Inline, IntSize, IntType, Optimize, Repr};
+
+/// Each attribute, along with its form before and since the format version it changed in.
+fn cases() -> Vec<(Attribute, u32, &'static str, &'static str)> {
+    vec![
+        (
+            Attribute::Repr(vec![Repr::C, Repr::Int(IntType::Signed(IntSize::X8))]),
+            43,
+            "#[attr = Repr([ReprC, ReprInt(SignedInt(I8))])]\n",
+            "#[repr(C, i8)]",
+        ),
+        (
+            Attribute::Repr(vec![Repr::Packed(2), Repr::Align(8)]),
+            43,
+            "#[attr = Repr([ReprPacked(Align(2 bytes)), ReprAlign(Align(8 bytes))])]\n",
+            "#[repr(packed(2), align(8))]",
+        ),
+        (
+            Attribute::Repr(vec![
+                Repr::Int(IntType::Unsigned(IntSize::Size)),
+                Repr::Transparent,
+            ]),
+            43,
+            "#[attr = Repr([ReprInt(UnsignedInt(Usize)), ReprTransparent])]\n",
+            "#[repr(usize, transparent)]",
+        ),
+        (
+            Attribute::Inline(Inline::Hint),
+            48,
+            "#[inline]",
+            "#[attr = Inline(Hint)]",
+        ),
+        (
+            Attribute::Inline(Inline::Always),
+            48,
+            "#[inline(always)]",
+            "#[attr = Inline(Always)]",
+        ),
+        (
+            Attribute::Inline(Inline::Never),
+            48,
+            "#[inline(never)]",
+            "#[attr = Inline(Never)]",
+        ),
+        (
+            Attribute::Optimize(Optimize::DoNotOptimize),
+            49,
+            "#[optimize(none)]",
+            "#[attr = Optimize(DoNotOptimize)]",
+        ),
+        (
+            Attribute::Optimize(Optimize::Speed),
+            49,
+            "#[optimize(speed)]",
+            "#[attr = Optimize(Speed)]",
+        ),
+        (
+            Attribute::Optimize(Optimize::Size),
+            49,
+            "#[optimize(size)]",
+            "#[attr = Optimize(Size)]",
+        ),
+        (Attribute::Cold, 50, "#[cold]", "#[attr = Cold]"),
+        (
+            Attribute::MustUse { reason: None },
+            52,
+            "#[must_use]",
+            "#[attr = MustUse]",
+        ),
+        (
+            Attribute::MustUse {
+                reason: Some("use me".to_owned()),
+            },
+            52,
+            "#[must_use = \"use me\"]",
+            "#[attr = MustUse {reason: \"use me\"}]",
+        ),
+    ]
+}
+
+#[test]
+fn parse_every_form() {
+    for (attribute, _, before, since) in cases() {
+        assert_eq!(
+            Attribute::parse(before),
+            attribute,
+            "failed to parse {before:?}"
+        );
+        assert_eq!(
+            Attribute::parse(since),
+            attribute,
+            "failed to parse {since:?}"
+        );
+    }
+}
+
+#[test]
+fn render_every_form() {
+    for (attribute, changed_in, before, since) in cases() {
+        assert_eq!(attribute.render(changed_in - 1).as_deref(), Some(before));
+        assert_eq!(attribute.render(changed_in).as_deref(), Some(since));
+    }
+}
+
+#[test]
+fn repr_rust_is_omitted_since_v43() {
+    let attribute = Attribute::parse("#[attr = Repr([ReprRust])]\n");
+
+    assert_eq!(attribute, Attribute::Repr(vec![Repr::Rust]));
+    assert_eq!(
+        attribute.render(42).as_deref(),
+        Some("#[attr = Repr([ReprRust])]\n")
+    );
+    assert_eq!(attribute.render(43), None);
+
+    assert_eq!(
+        Attribute::parse("#[attr = Repr([ReprRust, ReprC])]\n")
+            .render(43)
+            .as_deref(),
+        Some("#[repr(C)]"),
+    );
+}
+
+#[test]
+fn unrecognized_attributes_are_unchanged() {
+    for attr in [
+        "#[doc(hidden)]",
+        "#[non_exhaustive]",
+        "#[optimize(unknown)]",
+        "#[attr = Repr([ReprUnknown])]\n",
+        "#[repr(i7)]",
+    ] {
+        let attribute = Attribute::parse(attr);
+
+        assert_eq!(attribute, Attribute::Other(attr.to_owned()));
+
+        for version in [41, 53] {
+            assert_eq!(attribute.render(version).as_deref(), Some(attr));
+        }
+    }
+}
+
+#[test]
+fn must_use_string_literals() {
+    for (before, reason) in [
+        (r#"#[must_use = "say \"hi\""]"#, "say \"hi\""),
+        (
+            r#"#[must_use = "line\nbreak\ttab\\"]"#,
+            "line\nbreak\ttab\\",
+        ),
+        (r#"#[must_use = "ends with \"]"]"#, "ends with \"]"),
+        (r#"#[must_use = "\x41\u{1F600}\u{1_F600}"]"#, "A😀😀"),
+        ("#[must_use = \"wrapped \\\n    line\"]", "wrapped line"),
+        (
+            r##"#[must_use = r#"raw "quoted" \n"#]"##,
+            r#"raw "quoted" \n"#,
+        ),
+        (r#"#[must_use = r"raw"]"#, "raw"),
+        (r#"#[must_use = r"x"]"#, "x"),
+    ] {
+        let attribute = Attribute::parse(before);
+
+        assert_eq!(
+            attribute,
+            Attribute::MustUse {
+                reason: Some(reason.to_owned())
+            },
+            "failed to parse {before:?}",
+        );
+
+        // Since v52, Rustdoc escapes the message with `Debug`.
+        assert_eq!(
+            attribute.render(52).unwrap(),
+            format!("#[attr = MustUse {{reason: {reason:?}}}]"),
+        );
+
+        // The rendered forms should parse back to the same attribute.
+        for version in [51, 52] {
+            let rendered = attribute.render(version).unwrap();
+
+            assert_eq!(Attribute::parse(&rendered), attribute, "{rendered:?}");
+        }
+    }
+}
+
+#[test]
+fn invalid_must_use_string_literals() {
+    for attr in [
+        r#"#[must_use = "unterminated]"#,
+        r#"#[must_use = "escaped quote\"]"#,
+        r#"#[must_use = "\"]"#,
+        r#"#[must_use = "two" "literals"]"#,
+        r#"#[must_use = "bad escape \q"]"#,
+        r#"#[must_use = "\xFF"]"#,
+        r#"#[must_use = "\u{110000}"]"#,
+        r##"#[must_use = r#"raw"]"##,
+        "#[must_use = message]",
+    ] {
+        assert_eq!(
+            Attribute::parse(attr),
+            Attribute::Other(attr.to_owned()),
+            "{attr:?}"
+        );
+    }
+}
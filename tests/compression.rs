@@ -0,0 +1,85 @@
+//! Tests that compressed `--input` and `--output` files round-trip.
+
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use migrate_rustdoc_json::compression::{self, Compression, Output};
+
+const JSON: &str = r#"{"format_version":53}"#;
+
+fn temp_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name)
+}
+
+/// Writes [`JSON`] to `path` with [`Output`], returning the raw bytes that were written.
+fn write(path: &Path) -> Vec<u8> {
+    let mut output = Output::create(Some(path)).unwrap();
+    output.write_all(JSON.as_bytes()).unwrap();
+    output.finish().unwrap();
+
+    std::fs::read(path).unwrap()
+}
+
+fn read(path: &Path) -> String {
+    let mut json = String::new();
+
+    compression::open_input(path)
+        .unwrap()
+        .read_to_string(&mut json)
+        .unwrap();
+
+    json
+}
+
+#[test]
+fn compression_from_extension() {
+    for (path, expected) in [
+        ("crate.json", Compression::None),
+        ("crate.json.gz", Compression::Gzip),
+        ("crate.json.zst", Compression::Zstd),
+        ("crate", Compression::None),
+    ] {
+        assert_eq!(
+            Compression::from_extension(path.as_ref()),
+            expected,
+            "{path}"
+        );
+    }
+}
+
+#[test]
+fn round_trip_by_extension() {
+    for name in [
+        "round_trip.json",
+        "round_trip.json.gz",
+        "round_trip.json.zst",
+    ] {
+        let path = temp_path(name);
+        let bytes = write(&path);
+
+        assert_eq!(
+            Compression::from_magic_bytes(&bytes),
+            Compression::from_extension(&path),
+            "`{name}` was not compressed according to its extension",
+        );
+
+        assert_eq!(read(&path), JSON, "`{name}` did not round-trip");
+    }
+}
+
+#[test]
+fn detect_by_magic_bytes() {
+    for extension in ["gz", "zst"] {
+        let compressed_path = temp_path(&format!("magic_bytes.json.{extension}"));
+        let bytes = write(&compressed_path);
+
+        // Copy the compressed file to a path without a compression extension, so that it can only
+        // be detected by its magic bytes.
+        let renamed_path = temp_path(&format!("magic_bytes_{extension}.json"));
+        std::fs::write(&renamed_path, bytes).unwrap();
+
+        assert_eq!(read(&renamed_path), JSON, "`.{extension}` was not detected");
+    }
+}
@@ -0,0 +1,143 @@
+//! Tests that filters prune the migrated `Crate` and rewrite references to removed items.
+
+mod harness;
+
+use migrate_rustdoc_json::filter::Filter;
+use serde_json::{Value, json};
+
+use self::harness::fixture::{item, minimal_crate, unit_struct};
+
+/// A small `Crate` with a public and a private module, a struct with a private field, and paths
+/// from an external crate.
+fn crate_() -> Value {
+    let module = |items: &[u32]| json!({ "module": { "is_crate": false, "items": items, "is_stripped": false } });
+
+    let mut crate_ = minimal_crate(
+        53,
+        [
+            item(1, "public", module(&[3])),
+            item(2, "private", module(&[4])),
+        ],
+    );
+
+    crate_["includes_private"] = json!(true);
+
+    let index = &mut crate_["index"];
+
+    index["1"]["links"] = json!({ "`Private`": 4 });
+    index["2"]["visibility"] = json!("crate");
+
+    index["3"] = item(
+        3,
+        "Public",
+        json!({
+            "struct": {
+                "kind": { "tuple": [5, 6] },
+                "generics": { "params": [], "where_predicates": [] },
+                "impls": [],
+            },
+        }),
+    );
+    index["4"] = item(4, "Private", unit_struct());
+    index["5"] = item(
+        5,
+        "0",
+        json!({ "struct_field": { "resolved_path": { "path": "Vec", "id": 7, "args": null } } }),
+    );
+    index["6"] = item(6, "1", json!({ "struct_field": { "primitive": "u8" } }));
+    index["6"]["visibility"] = json!({ "restricted": { "parent": 1, "path": "::public" } });
+
+    crate_["paths"] = json!({
+        "0": { "crate_id": 0, "path": ["my_crate"], "kind": "module" },
+        "1": { "crate_id": 0, "path": ["my_crate", "public"], "kind": "module" },
+        "2": { "crate_id": 0, "path": ["my_crate", "private"], "kind": "module" },
+        "3": { "crate_id": 0, "path": ["my_crate", "public", "Public"], "kind": "struct" },
+        "4": { "crate_id": 0, "path": ["my_crate", "private", "Private"], "kind": "struct" },
+        "7": { "crate_id": 1, "path": ["alloc", "vec", "Vec"], "kind": "struct" },
+        "8": { "crate_id": 2, "path": ["core", "cell", "Cell"], "kind": "struct" },
+    });
+    crate_["external_crates"] = json!({
+        "1": { "name": "alloc", "html_root_url": null },
+        "2": { "name": "core", "html_root_url": null },
+    });
+
+    crate_
+}
+
+fn apply(filter: Filter) -> Value {
+    let mut crate_ = crate_();
+    filter.apply(&mut crate_).unwrap();
+    crate_
+}
+
+fn keys(value: &Value) -> Vec<&str> {
+    let mut keys: Vec<&str> = value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+#[test]
+fn empty_filter_keeps_everything() {
+    assert_eq!(apply(Filter::default()), crate_());
+}
+
+#[test]
+fn strip_private() {
+    let crate_ = apply(Filter {
+        strip_private: true,
+        ..Default::default()
+    });
+
+    // The private module and its children are removed, along with the private field.
+    assert_eq!(keys(&crate_["index"]), ["0", "1", "3", "5"]);
+    assert_eq!(crate_["index"]["0"]["inner"]["module"]["items"], json!([1]));
+    assert_eq!(crate_["index"]["1"]["links"], json!({}));
+    assert_eq!(
+        crate_["index"]["3"]["inner"]["struct"]["kind"],
+        json!({ "tuple": [5, null] })
+    );
+    assert_eq!(crate_["includes_private"], json!(false));
+
+    assert_eq!(keys(&crate_["paths"]), ["0", "1", "3", "7", "8"]);
+}
+
+#[test]
+fn strip_external_paths() {
+    let crate_ = apply(Filter {
+        strip_external_paths: true,
+        ..Default::default()
+    });
+
+    // `Vec` is still referenced by a field, but `Cell` and `core` are not.
+    assert_eq!(keys(&crate_["paths"]), ["0", "1", "2", "3", "4", "7"]);
+    assert_eq!(keys(&crate_["external_crates"]), ["1"]);
+    assert_eq!(crate_["includes_private"], json!(true));
+}
+
+#[test]
+fn only_module() {
+    let crate_ = apply(Filter {
+        only_module: Some("my_crate::public".to_owned()),
+        ..Default::default()
+    });
+
+    assert_eq!(keys(&crate_["index"]), ["0", "1", "3", "5", "6"]);
+    assert_eq!(crate_["index"]["0"]["inner"]["module"]["items"], json!([1]));
+}
+
+#[test]
+fn only_module_not_found() {
+    let mut crate_ = crate_();
+
+    let filter = Filter {
+        only_module: Some("my_crate::missing".to_owned()),
+        ..Default::default()
+    };
+
+    assert!(filter.apply(&mut crate_).is_err());
+}
@@ -0,0 +1,219 @@
+//! Tests that fragments of a `Crate` can be migrated on their own.
+
+mod harness;
+
+use migrate_rustdoc_json::{
+    fragment::FragmentKind,
+    migrations::{self, MAXIMUM_VERSION, MINIMUM_VERSION},
+    reporter::{Reporter, Verbosity},
+};
+use serde_json::{Value, json};
+
+use self::harness::fixture::{item, unit_struct};
+
+fn migrate_fragment(
+    kind: FragmentKind,
+    fragment: Value,
+    from_version: u32,
+    to_version: u32,
+) -> Value {
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let migrated = migrations::migrate_fragment(
+        kind,
+        &fragment.to_string(),
+        from_version,
+        to_version,
+        &mut reporter,
+    )
+    .unwrap();
+
+    assert_eq!(
+        reporter.versions().first().copied(),
+        Some(from_version),
+        "the reporter should record the original format version",
+    );
+    assert_eq!(reporter.versions().last().copied(), Some(to_version));
+
+    serde_json::from_str(&migrated).unwrap()
+}
+
+#[test]
+fn unchanged_type() {
+    let type_ = json!({
+        "borrowed_ref": {
+            "lifetime": null,
+            "is_mutable": false,
+            "type": { "primitive": "str" },
+        },
+    });
+
+    assert_eq!(
+        migrate_fragment(
+            FragmentKind::Type,
+            type_.clone(),
+            MINIMUM_VERSION,
+            MAXIMUM_VERSION
+        ),
+        type_,
+    );
+}
+
+/// Returns an `Item` for a unit struct with the given attributes.
+fn struct_item(attrs: &[&str]) -> Value {
+    let mut item = item(3, "ReprC", unit_struct());
+    item["attrs"] = json!(attrs);
+    item
+}
+
+#[test]
+fn item_attrs() {
+    let item = struct_item(&["#[attr = Repr([ReprC])]\n"]);
+
+    let migrated = migrate_fragment(FragmentKind::Item, item, 42, 43);
+
+    assert_eq!(migrated["attrs"], json!(["#[repr(C)]"]));
+    assert_eq!(migrated["name"], "ReprC");
+}
+
+#[test]
+fn unchanged_attr_families_keep_their_text() {
+    // v43 only changed `#[repr(...)]`, so the `#[must_use]` string literals are kept as written,
+    // rather than re-rendered with `Debug`.
+    let attrs = [
+        r#"#[must_use = r"raw \n"]"#,
+        r#"#[must_use = "escaped \x41"]"#,
+        "#[inline(always)]",
+    ];
+
+    let migrated = migrate_fragment(FragmentKind::Item, struct_item(&attrs), 42, 43);
+
+    assert_eq!(migrated["attrs"], json!(attrs));
+
+    // They are only converted in the hop where their form changed.
+    let migrated = migrate_fragment(FragmentKind::Item, struct_item(&attrs), 42, 52);
+
+    assert_eq!(
+        migrated["attrs"],
+        json!([
+            r#"#[attr = MustUse {reason: "raw \\n"}]"#,
+            r#"#[attr = MustUse {reason: "escaped A"}]"#,
+            "#[attr = Inline(Always)]",
+        ]),
+    );
+}
+
+#[test]
+fn new_attr_forms_are_not_converted_early() {
+    // v42 only used the `Debug` form of `#[repr(...)]`, so the pretty form is kept as written.
+    let attrs = ["#[repr(C,i8)]", "#[repr(Rust)]"];
+
+    let migrated = migrate_fragment(FragmentKind::Item, struct_item(&attrs), 42, 43);
+
+    assert_eq!(migrated["attrs"], json!(attrs));
+}
+
+#[test]
+fn unrecognized_attrs_are_item_caveats() {
+    let item = struct_item(&["#[attr = Repr([ReprUnknown])]\n", "#[doc(hidden)]"]);
+
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let migrated =
+        migrations::migrate_fragment(FragmentKind::Item, &item.to_string(), 42, 43, &mut reporter)
+            .unwrap();
+
+    // Unrecognized attributes are left unchanged.
+    assert_eq!(
+        serde_json::from_str::<Value>(&migrated).unwrap()["attrs"],
+        item["attrs"],
+    );
+
+    // Only the attribute that looks like a `#[repr(...)]` is reported.
+    let [caveat] = reporter.caveats() else {
+        panic!("expected one caveat, got {:?}", reporter.caveats());
+    };
+
+    assert_eq!(caveat.item, Some(3));
+    assert_eq!(caveat.while_migrating_to, 43);
+    assert!(
+        caveat
+            .message
+            .contains(r##""#[attr = Repr([ReprUnknown])]\n""##),
+        "{caveat:?}"
+    );
+}
+
+#[test]
+fn must_use_string_literals_are_unescaped() {
+    let item = struct_item(&[r#"#[must_use = r"x"]"#, r#"#[must_use = "\"]"#]);
+
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let migrated =
+        migrations::migrate_fragment(FragmentKind::Item, &item.to_string(), 51, 52, &mut reporter)
+            .unwrap();
+
+    // The raw string is converted, but the unterminated string literal is left unchanged.
+    assert_eq!(
+        serde_json::from_str::<Value>(&migrated).unwrap()["attrs"],
+        json!([r#"#[attr = MustUse {reason: "x"}]"#, r#"#[must_use = "\"]"#]),
+    );
+
+    let [caveat] = reporter.caveats() else {
+        panic!("expected one caveat, got {:?}", reporter.caveats());
+    };
+
+    assert_eq!(caveat.item, Some(3));
+    assert_eq!(caveat.while_migrating_to, 52);
+}
+
+#[test]
+fn same_version() {
+    let generics = json!({ "params": [], "where_predicates": [] });
+
+    assert_eq!(
+        migrate_fragment(
+            FragmentKind::Generics,
+            generics.clone(),
+            MAXIMUM_VERSION,
+            MAXIMUM_VERSION
+        ),
+        generics,
+    );
+}
+
+#[test]
+fn wrong_kind() {
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let error = migrations::migrate_fragment(
+        FragmentKind::Item,
+        r#"{ "primitive": "u8" }"#,
+        MINIMUM_VERSION,
+        MAXIMUM_VERSION,
+        &mut reporter,
+    )
+    .unwrap_err();
+
+    assert!(
+        format!("{error:#}").contains("failed to deserialize `Item` from JSON"),
+        "unexpected error: {error:#}",
+    );
+}
+
+#[test]
+fn downgrading() {
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    assert!(
+        migrations::migrate_fragment(
+            FragmentKind::Type,
+            r#"{ "primitive": "u8" }"#,
+            MAXIMUM_VERSION,
+            MINIMUM_VERSION,
+            &mut reporter,
+        )
+        .is_err()
+    );
+}
@@ -0,0 +1,77 @@
+//! Builders for small, hand-written Rustdoc JSON documents.
+//!
+//! This module only depends on `serde_json`, so that it can also be included by the fuzz targets.
+
+use serde_json::{Map, Value, json};
+
+/// Returns the JSON of a crate named `my_crate` in the given format version.
+///
+/// The crate root has the `Id` 0 and contains `items`, which are also added to `index`. Only the
+/// crate root is listed in `paths`.
+pub fn minimal_crate(format_version: u32, items: impl IntoIterator<Item = Value>) -> Value {
+    let items: Vec<Value> = items.into_iter().collect();
+    let ids: Vec<&Value> = items.iter().map(|item| &item["id"]).collect();
+
+    let root = item(
+        0,
+        "my_crate",
+        json!({
+            "module": { "is_crate": true, "items": ids, "is_stripped": false },
+        }),
+    );
+
+    let mut index = Map::new();
+
+    for item in std::iter::once(root).chain(items) {
+        index.insert(item["id"].to_string(), item);
+    }
+
+    let mut crate_ = json!({
+        "root": 0,
+        "crate_version": null,
+        "includes_private": false,
+        "index": index,
+        "paths": {
+            "0": { "crate_id": 0, "path": ["my_crate"], "kind": "module" },
+        },
+        "external_crates": {},
+        "format_version": format_version,
+    });
+
+    // `Crate::target` was added in v44.
+    if format_version >= 44 {
+        crate_["target"] = json!({
+            "triple": "x86_64-unknown-linux-gnu",
+            "target_features": [],
+        });
+    }
+
+    crate_
+}
+
+/// Returns the JSON of a public item of the local crate, without docs or attributes.
+pub fn item(id: u32, name: &str, inner: Value) -> Value {
+    json!({
+        "id": id,
+        "crate_id": 0,
+        "name": name,
+        "span": { "filename": "src/lib.rs", "begin": [1, 1], "end": [2, 1] },
+        "visibility": "public",
+        "docs": null,
+        "links": {},
+        "attrs": [],
+        "deprecation": null,
+        "inner": inner,
+    })
+}
+
+/// Returns the `inner` of a unit struct without generics or `impl`s.
+pub fn unit_struct() -> Value {
+    json!({
+        "struct": {
+            "kind": "unit",
+            "generics": { "params": [], "where_predicates": [] },
+            "impls": [],
+        },
+    })
+}
@@ -1,4 +1,9 @@
+// Each test only uses part of the harness.
+#![allow(dead_code)]
+
+pub mod fixture;
 mod json;
+mod snapshot;
 mod toolchains;
 
 use std::{ops::ControlFlow, path::PathBuf};
@@ -7,12 +12,17 @@ use jsonpath_rust::JsonPath;
 use pretty_assertions::assert_eq;
 
 use self::json::{GeneratedAndMigrated, generate_and_migrate_to};
+use self::snapshot::assert_snapshot;
 use self::toolchains::needs_toolchain;
 
 /// A builder that configures and runs a migration test.
 ///
 /// The Rust file used to generate the Rustdoc JSON is assumed to be stored at `tests/vXX/vXX.rs`,
 /// where `XX` is the current format version passed to [`MigrationTest::new()`].
+///
+/// In addition to the query and custom tests, the entire migrated JSON is compared against a
+/// snapshot stored at `tests/snapshots/vXX_to_vYY.json`. Run the tests with `UPDATE_SNAPSHOTS=1`
+/// to update the snapshots after an intentional change.
 pub(crate) struct MigrationTest {
     original_format_version: u32,
     migrated_format_version: u32,
@@ -84,6 +94,13 @@ impl MigrationTest {
             return;
         };
 
+        let snapshot_name = self
+            .source
+            .file_stem()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+
         let GeneratedAndMigrated {
             original_json,
             new_json,
@@ -138,6 +155,8 @@ impl MigrationTest {
         for custom_test in self.custom_tests {
             custom_test(&original_json, &new_json, &migrated_json);
         }
+
+        assert_snapshot(&snapshot_name, &migrated_json);
     }
 }
 
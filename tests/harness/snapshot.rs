@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use pretty_assertions::assert_eq;
+use serde_json::{Map, Value};
+
+/// Asserts that the normalized `migrated_json` matches the snapshot stored at
+/// `tests/snapshots/{name}.json`.
+///
+/// If `UPDATE_SNAPSHOTS=1` is set, the snapshot will be created or overwritten with the new migrated
+/// JSON instead of failing the test. Otherwise a missing snapshot fails the test.
+pub fn assert_snapshot(name: &str, migrated_json: &Value) {
+    let snapshot_path = snapshot_path(name);
+
+    // End the file with a newline, so it plays nicely with Git and text editors.
+    let actual = serde_json::to_string_pretty(&normalize(migrated_json)).unwrap() + "\n";
+
+    // This is read at runtime, not compile time, so that it takes effect without a rebuild.
+    let update_snapshots = std::env::var_os("UPDATE_SNAPSHOTS").is_some_and(|value| value == "1");
+
+    if update_snapshots {
+        std::fs::create_dir_all(snapshot_path.parent().unwrap()).unwrap();
+        std::fs::write(&snapshot_path, actual).unwrap();
+
+        eprintln!("Wrote snapshot {}", snapshot_path.display());
+
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&snapshot_path).unwrap_or_else(|error| {
+        panic!(
+            "could not read snapshot {}, run with `UPDATE_SNAPSHOTS=1` to create it: {error}",
+            snapshot_path.display(),
+        )
+    });
+
+    assert_eq!(
+        expected,
+        actual,
+        "migrated JSON does not match snapshot {}, run with `UPDATE_SNAPSHOTS=1` to update it",
+        snapshot_path.display(),
+    );
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(name)
+        .with_extension("json")
+}
+
+/// Removes all non-deterministic and host-specific information from Rustdoc JSON, so that it may
+/// be compared across runs and operating systems.
+fn normalize(json: &Value) -> Value {
+    let mut json = sort_keys(json);
+
+    // The target depends on the host that generated the original JSON, so we replace it with a
+    // placeholder.
+    if let Some(target) = json.get_mut("target") {
+        target["triple"] = Value::String("<triple>".to_owned());
+        target["target_features"] = Value::Array(Vec::new());
+    }
+
+    json
+}
+
+/// Recursively sorts the keys of all objects.
+///
+/// `serde_json` may preserve insertion order depending on its enabled features, so this ensures
+/// the order is always the same, no matter what order Rustdoc or `migrate_rustdoc_json` wrote the
+/// keys.
+fn sort_keys(json: &Value) -> Value {
+    match json {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+
+            entries.sort_unstable_by_key(|(key, _)| *key);
+
+            Value::Object(Map::from_iter(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), sort_keys(value))),
+            ))
+        }
+        Value::Array(array) => Value::Array(array.iter().map(sort_keys).collect()),
+        _ => json.clone(),
+    }
+}
@@ -0,0 +1,177 @@
+//! Tests that `--lenient` drops the items that cannot be deserialized, and migrates the rest.
+
+mod harness;
+
+use migrate_rustdoc_json::{
+    migrations,
+    output::OutputOptions,
+    reporter::{Reporter, Verbosity},
+};
+use serde_json::{Value, json};
+
+use self::harness::fixture::{item, minimal_crate, unit_struct};
+
+/// Returns the JSON of a v52 crate whose root module contains a unit struct and an item with an
+/// unknown kind.
+fn crate_() -> Value {
+    minimal_crate(
+        52,
+        [
+            item(1, "Unit", unit_struct()),
+            item(2, "Unknown", json!({ "from_the_future": {} })),
+        ],
+    )
+}
+
+fn migrate(crate_: &Value, lenient: bool, reporter: &mut Reporter) -> anyhow::Result<Value> {
+    let json = crate_.to_string();
+
+    let output = migrations::migrate_up_streaming(
+        || Ok(json.as_bytes()),
+        || Ok(Vec::new()),
+        None,
+        lenient,
+        53,
+        &OutputOptions::default(),
+        reporter,
+    )?;
+
+    Ok(serde_json::from_slice(&output).unwrap())
+}
+
+#[test]
+fn drops_invalid_items() {
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let migrated = migrate(&crate_(), true, &mut reporter).unwrap();
+
+    assert_eq!(migrated["format_version"], 53);
+    assert!(migrated["index"].get("1").is_some());
+    assert!(migrated["index"].get("2").is_none());
+
+    // References to the dropped item are removed.
+    assert_eq!(
+        migrated["index"]["0"]["inner"]["module"]["items"],
+        json!([1])
+    );
+
+    let [caveat] = reporter.caveats() else {
+        panic!("expected one caveat, got {:?}", reporter.caveats());
+    };
+
+    assert_eq!(caveat.item, Some(2));
+    assert_eq!(caveat.while_migrating_to, 52);
+    assert!(
+        caveat.message.contains("could not be deserialized as v52"),
+        "{caveat:?}"
+    );
+    assert!(caveat.message.contains("from_the_future"), "{caveat:?}");
+}
+
+#[test]
+fn drops_references_to_invalid_items() {
+    let mut crate_ = crate_();
+    crate_["index"]["1"]["links"] = json!({ "Unknown": 2 });
+    crate_["index"]["3"] = item(
+        3,
+        "Unknown",
+        json!({ "use": { "source": "crate::Unknown", "name": "Unknown", "id": 2, "is_glob": false } }),
+    );
+
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let migrated = migrate(&crate_, true, &mut reporter).unwrap();
+
+    assert_eq!(migrated["index"]["1"]["links"], json!({}));
+    assert_eq!(migrated["index"]["3"]["inner"]["use"]["id"], Value::Null);
+    assert_eq!(reporter.caveats().len(), 1);
+}
+
+#[test]
+fn drops_paths_of_invalid_items() {
+    let mut crate_ = crate_();
+    crate_["paths"]["2"] =
+        json!({ "crate_id": 0, "path": ["my_crate", "Unknown"], "kind": "struct" });
+
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let migrated = migrate(&crate_, true, &mut reporter).unwrap();
+
+    assert!(migrated["paths"].get("2").is_none());
+    assert!(migrated["paths"].get("0").is_some());
+}
+
+#[test]
+fn drops_invalid_paths_entries() {
+    let mut crate_ = crate_();
+    crate_["index"]["3"] = item(
+        3,
+        "Future",
+        json!({ "use": { "source": "other::Future", "name": "Future", "id": 4, "is_glob": false } }),
+    );
+    crate_["paths"]["4"] =
+        json!({ "crate_id": 1, "path": ["other", "Future"], "kind": "from_the_future" });
+    crate_["external_crates"]["1"] = json!({ "name": "other", "html_root_url": null });
+
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    assert!(migrate(&crate_, false, &mut reporter).is_err());
+
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let migrated = migrate(&crate_, true, &mut reporter).unwrap();
+
+    assert!(migrated["paths"].get("4").is_none());
+    assert_eq!(migrated["index"]["3"]["inner"]["use"]["id"], Value::Null);
+    assert_eq!(migrated["external_crates"], json!({}));
+
+    let [item_caveat, paths_caveat] = reporter.caveats() else {
+        panic!("expected two caveats, got {:?}", reporter.caveats());
+    };
+
+    assert_eq!(item_caveat.item, Some(2));
+    assert_eq!(paths_caveat.item, Some(4));
+    assert!(
+        paths_caveat
+            .message
+            .contains("dropped the `paths` entry, as it could not be deserialized as v52"),
+        "{paths_caveat:?}"
+    );
+}
+
+#[test]
+fn strict_by_default() {
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    assert!(migrate(&crate_(), false, &mut reporter).is_err());
+}
+
+#[test]
+fn invalid_root() {
+    let mut crate_ = crate_();
+    crate_["index"]["0"]["inner"] = json!({ "from_the_future": {} });
+
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let error = migrate(&crate_, true, &mut reporter).unwrap_err();
+
+    assert!(
+        format!("{error:#}").contains("the crate root, item 0, could not be deserialized"),
+        "unexpected error: {error:#}",
+    );
+}
+
+#[test]
+fn requires_format_version() {
+    let mut crate_ = crate_();
+    crate_.as_object_mut().unwrap().remove("format_version");
+
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+
+    let error = migrate(&crate_, true, &mut reporter).unwrap_err();
+
+    assert!(
+        format!("{error:#}").contains("pass `--from-version`"),
+        "unexpected error: {error:#}",
+    );
+}
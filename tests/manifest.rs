@@ -0,0 +1,169 @@
+//! Tests that migration manifests are written next to the output and can be read back.
+
+mod harness;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use migrate_rustdoc_json::{
+    filter::Filter,
+    manifest::{self, Hop, Manifest},
+    reporter::{Caveat, Reporter, Verbosity},
+};
+
+use self::harness::fixture::{item, minimal_crate, unit_struct};
+
+#[test]
+fn manifest_path_for_output() {
+    for (output, expected) in [
+        ("migrated.json", "migrated.json.migration.json"),
+        (
+            "out/migrated.json.gz",
+            "out/migrated.json.gz.migration.json",
+        ),
+    ] {
+        assert_eq!(Manifest::path_for(Path::new(output)), Path::new(expected));
+    }
+}
+
+#[test]
+fn manifest_round_trip() {
+    let manifest = Manifest {
+        tool: "migrate_rustdoc_json".to_owned(),
+        tool_version: "0.0.0".to_owned(),
+        source_version: 45,
+        source_sha256: "0".repeat(64),
+        target_version: 48,
+        hops: vec![Hop { from: 45, to: 46 }, Hop { from: 46, to: 48 }],
+        filter: Filter {
+            strip_private: true,
+            strip_external_paths: false,
+            only_module: Some("my_crate::foo".to_owned()),
+        },
+        caveats: vec![
+            Caveat {
+                message: "an example caveat".to_owned(),
+                while_migrating_to: 46,
+                item: None,
+            },
+            Caveat {
+                message: "an example item caveat".to_owned(),
+                while_migrating_to: 48,
+                item: Some(12),
+            },
+        ],
+    };
+
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("round_trip.migration.json");
+
+    manifest.write(&path).unwrap();
+
+    assert_eq!(Manifest::read(&path).unwrap(), manifest);
+}
+
+#[test]
+fn manifest_records_migration() {
+    let mut reporter = Reporter::new(Verbosity::Quiet);
+    reporter.begin_migrating(45);
+    reporter.migrating_to(46);
+    reporter.item_caveat(12, "an example item caveat".to_owned());
+    reporter.migrating_to(48);
+
+    let filter = Filter {
+        strip_private: true,
+        strip_external_paths: true,
+        only_module: None,
+    };
+
+    let manifest = Manifest::new("0".repeat(64), &filter, &reporter).unwrap();
+
+    assert_eq!(manifest.source_version, 45);
+    assert_eq!(manifest.target_version, 48);
+    assert_eq!(
+        manifest.hops,
+        [Hop { from: 45, to: 46 }, Hop { from: 46, to: 48 }],
+    );
+    assert_eq!(manifest.source_sha256, "0".repeat(64));
+    assert_eq!(manifest.filter, filter);
+    assert_eq!(
+        manifest.caveats,
+        [Caveat {
+            message: "an example item caveat".to_owned(),
+            while_migrating_to: 46,
+            item: Some(12),
+        }],
+    );
+}
+
+#[test]
+fn hash_file_of_empty_file() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("empty_input.json");
+    fs::write(&path, "").unwrap();
+
+    assert_eq!(
+        manifest::hash_file(&path).unwrap(),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+    );
+}
+
+#[test]
+fn manifest_hashes_input_migrated_in_place() {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("in_place.json");
+    fs::write(
+        &path,
+        minimal_crate(52, [item(1, "Unit", unit_struct())]).to_string(),
+    )
+    .unwrap();
+
+    let original_sha256 = manifest::hash_file(&path).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_migrate_rustdoc_json"))
+        .arg("--input")
+        .arg(&path)
+        .arg("--output")
+        .arg(&path)
+        .arg("--to-version")
+        .arg("53")
+        .arg("--manifest")
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "migrating in place failed:\n{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    let manifest = Manifest::read(&Manifest::path_for(&path)).unwrap();
+
+    assert_eq!(manifest.source_sha256, original_sha256);
+    assert_ne!(manifest::hash_file(&path).unwrap(), original_sha256);
+    assert_eq!(manifest.hops, [Hop { from: 52, to: 53 }]);
+}
+
+#[test]
+fn filter_displays_as_arguments() {
+    for (filter, expected) in [
+        (Filter::default(), ""),
+        (
+            Filter {
+                strip_private: true,
+                ..Filter::default()
+            },
+            "--strip-private",
+        ),
+        (
+            Filter {
+                strip_private: true,
+                strip_external_paths: true,
+                only_module: Some("my_crate::foo".to_owned()),
+            },
+            "--strip-private --strip-external-paths --only-module my_crate::foo",
+        ),
+    ] {
+        assert_eq!(filter.to_string(), expected);
+    }
+}
@@ -0,0 +1,89 @@
+//! Tests that merging crates offsets their `Id`s and resolves references between them.
+
+mod harness;
+
+use migrate_rustdoc_json::merge::merge;
+use serde_json::{Value, json};
+
+use self::harness::fixture::{item, minimal_crate};
+
+/// A crate named `name` with a root module containing a single function, whose return type is the
+/// external item with `Id` 2.
+fn crate_(name: &str, external: &str) -> Value {
+    let function = item(
+        1,
+        "f",
+        json!({
+            "function": {
+                "sig": {
+                    "inputs": [],
+                    "output": { "resolved_path": { "path": "S", "id": 2, "args": null } },
+                },
+            },
+        }),
+    );
+
+    let mut crate_ = minimal_crate(53, [function]);
+
+    crate_["index"]["0"]["name"] = json!(name);
+    crate_["index"]["0"]["links"] = json!({ "`f`": 1 });
+
+    crate_["paths"] = json!({
+        "0": { "crate_id": 0, "path": [name], "kind": "module" },
+        "1": { "crate_id": 0, "path": [name, "f"], "kind": "function" },
+        "2": { "crate_id": 1, "path": [external, "S"], "kind": "struct" },
+    });
+    crate_["external_crates"] = json!({
+        "1": { "name": external, "html_root_url": null },
+    });
+
+    crate_
+}
+
+#[test]
+fn merge_resolves_references_between_crates() {
+    let mut b = crate_("b", "core");
+
+    // Make `b` document the `S` that `a` refers to.
+    b["paths"]["3"] = json!({ "crate_id": 0, "path": ["b", "S"], "kind": "struct" });
+
+    let workspace = merge(vec![crate_("a", "b"), b]).unwrap();
+
+    assert_eq!(workspace["format_version"], json!(53));
+
+    let a = &workspace["crates"]["a"];
+    let b = &workspace["crates"]["b"];
+
+    // `a` starts at 0, and `b` starts after `a`'s largest `Id`.
+    assert_eq!(a["root"], json!(0));
+    assert_eq!(b["root"], json!(3));
+    assert_eq!(b["index"]["4"]["id"], json!(4));
+    assert_eq!(b["index"]["3"]["links"]["`f`"], json!(4));
+    assert_eq!(b["index"]["3"]["inner"]["module"]["items"], json!([4]));
+
+    // `a`'s reference to `b::S` now points to `b`'s `Id` for it.
+    assert_eq!(
+        a["index"]["1"]["inner"]["function"]["sig"]["output"]["resolved_path"]["id"],
+        json!(6),
+    );
+    assert!(a["paths"].get("6").is_some());
+
+    // `b`'s reference to `core::S` is not resolved, only offset.
+    assert_eq!(
+        b["index"]["4"]["inner"]["function"]["sig"]["output"]["resolved_path"]["id"],
+        json!(5),
+    );
+}
+
+#[test]
+fn merge_rejects_duplicate_names() {
+    assert!(merge(vec![crate_("a", "core"), crate_("a", "core")]).is_err());
+}
+
+#[test]
+fn merge_rejects_different_format_versions() {
+    let mut b = crate_("b", "core");
+    b["format_version"] = json!(52);
+
+    assert!(merge(vec![crate_("a", "core"), b]).is_err());
+}
@@ -0,0 +1,95 @@
+//! Tests that migrating a `Crate` in parallel gives the same result as migrating it sequentially.
+
+mod harness;
+
+use migrate_rustdoc_json::{
+    reporter::{Reporter, Verbosity},
+    traits::MigrateUp,
+};
+use serde_json::Value;
+
+use self::harness::fixture::{item, minimal_crate, unit_struct};
+
+/// Returns the JSON of a crate with many structs, each with the attribute returned by `attr`.
+fn crate_with_attrs(format_version: u32, attr: impl Fn(u32) -> String) -> Value {
+    let items = (1..=500).map(|id| {
+        let mut item = item(id, &format!("Struct{id}"), unit_struct());
+        item["attrs"] = Value::from(vec![attr(id)]);
+        item
+    });
+
+    minimal_crate(format_version, items)
+}
+
+/// Generates a test that migrates a `Crate` of the given format version in parallel, and compares
+/// its `index`, `paths`, and item caveats to migrating them sequentially. `$item_caveats` is the
+/// number of item caveats expected.
+///
+/// Only `Crate::index` and `Crate::paths` are migrated in parallel, and every other `HashMap`
+/// sequentially, so migrating them on their own gives the sequential result.
+macro_rules! parallel_matches_sequential {
+    ($name:ident, $version:literal, $rustdoc_types:ident, $attr:expr, $item_caveats:literal) => {
+        #[test]
+        fn $name() {
+            let crate_: $rustdoc_types::Crate =
+                serde_json::from_value(crate_with_attrs($version, $attr)).unwrap();
+
+            let mut parallel_reporter = Reporter::new(Verbosity::Quiet);
+            let parallel = crate_.clone().migrate_up(&mut parallel_reporter);
+
+            let mut sequential_reporter = Reporter::new(Verbosity::Quiet);
+            let index = crate_.index.clone().migrate_up(&mut sequential_reporter);
+            let paths = crate_.paths.clone().migrate_up(&mut sequential_reporter);
+
+            assert_eq!(parallel.index, index);
+            assert_eq!(parallel.paths, paths);
+
+            // Caveats about the whole `Crate` are only reported by the parallel migration.
+            let item_caveats: Vec<_> = parallel_reporter
+                .caveats()
+                .iter()
+                .filter(|caveat| caveat.item.is_some())
+                .cloned()
+                .collect();
+
+            assert_eq!(item_caveats.len(), $item_caveats);
+            assert_eq!(
+                item_caveats,
+                sequential_reporter.caveats(),
+                "caveats should be reported in the same order",
+            );
+        }
+    };
+}
+
+// v42 to v43 uses the generated `MigrateUp` implementation for `Crate`, and the others use manual
+// ones. In the hops that change attributes, each attribute cannot be migrated, so every item
+// reports a caveat.
+parallel_matches_sequential!(
+    v42_to_v43,
+    42,
+    rustdoc_types_42,
+    |id| format!("#[attr = Repr([ReprUnknown({id})])]\n"),
+    500
+);
+parallel_matches_sequential!(
+    v43_to_v44,
+    43,
+    rustdoc_types_43,
+    |id| format!("#[doc(alias = \"{id}\")]"),
+    0
+);
+parallel_matches_sequential!(
+    v45_to_v46,
+    45,
+    rustdoc_types_45,
+    |id| format!("#[doc(alias = \"{id}\")]"),
+    0
+);
+parallel_matches_sequential!(
+    v46_to_v48,
+    46,
+    rustdoc_types_46,
+    |id| format!("#[inline(sometimes{id})]"),
+    500
+);
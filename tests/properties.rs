@@ -0,0 +1,255 @@
+//! Property-based tests that migrate arbitrary Rustdoc JSON through every supported hop.
+
+#[path = "properties/arbitrary.rs"]
+mod arbitrary;
+
+use std::{collections::BTreeMap, fmt};
+
+use migrate_rustdoc_json::{
+    migrations::{self, MAXIMUM_VERSION, SUPPORTED_VERSIONS, VersionedCrate},
+    output::OutputOptions,
+    reporter::{Reporter, Verbosity},
+    schema,
+    traits::MigrateTo,
+    view::CrateView,
+};
+use proptest::prelude::*;
+use serde::{
+    Deserialize, Deserializer,
+    de::{IgnoredAny, MapAccess, Visitor},
+};
+use serde_json::Value;
+
+/// Migrations between format versions with identical schemas.
+///
+/// Migrating across these hops should not change anything besides the format version.
+const LOSSLESS_HOPS: [(u32, u32); 3] = [
+    // v42 only added the `GenericArgs::ReturnTypeNotation` variant.
+    (41, 42),
+    // v46 only changed when Rustdoc emits `#[repr(transparent)]`.
+    (45, 46),
+    // v53 was bumped by mistake.
+    (52, 53),
+];
+
+fn migrate(json: &str, to_version: u32) -> String {
+    migrate_with_options(json, to_version, &OutputOptions::default())
+}
+
+fn migrate_with_options(json: &str, to_version: u32, options: &OutputOptions) -> String {
+    migrations::migrate_up(
+        json,
+        to_version,
+        options,
+        &mut Reporter::new(Verbosity::Quiet),
+    )
+    .unwrap_or_else(|error| panic!("failed to migrate JSON to v{to_version}: {error:?}\n{json}"))
+}
+
+fn parse(json: &str) -> Value {
+    serde_json::from_str(json).unwrap()
+}
+
+/// The `index` and `paths` of a `Crate`, used to check the order of their keys.
+#[derive(Deserialize)]
+struct IndexAndPaths {
+    index: OrderedKeys,
+    paths: OrderedKeys,
+}
+
+/// The keys of a JSON object, in the order they were written.
+///
+/// [`Value`] cannot be used for this, as it sorts its keys.
+struct OrderedKeys(Vec<String>);
+
+impl<'de> Deserialize<'de> for OrderedKeys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeysVisitor;
+
+        impl<'de> Visitor<'de> for KeysVisitor {
+            type Value = OrderedKeys;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut keys = Vec::new();
+
+                while let Some((key, IgnoredAny)) = map.next_entry::<String, IgnoredAny>()? {
+                    keys.push(key);
+                }
+
+                Ok(OrderedKeys(keys))
+            }
+        }
+
+        deserializer.deserialize_map(KeysVisitor)
+    }
+}
+
+/// Everything [`CrateView`] exposes about each item, formatted with [`Debug`], keyed by `Id`.
+fn view_items(view: &dyn CrateView) -> BTreeMap<u32, String> {
+    view.items()
+        .map(|item| {
+            let summary = format!(
+                "{:?} {:?} {:?} {:?} {:?} {:?}",
+                item.name(),
+                item.docs(),
+                item.kind(),
+                item.visibility(),
+                item.span(),
+                item.attrs(),
+            );
+
+            (item.id(), summary)
+        })
+        .collect()
+}
+
+proptest! {
+    // Each case migrates the JSON many times, so we use fewer cases than the default.
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Migrating from the oldest to any supported format version should never fail.
+    #[test]
+    fn migrations_are_total(crate_ in arbitrary::crate_()) {
+        let original = serde_json::to_string(&crate_).unwrap();
+
+        for &version in SUPPORTED_VERSIONS {
+            let migrated = parse(&migrate(&original, version));
+
+            prop_assert_eq!(&migrated["format_version"], &Value::from(version));
+        }
+    }
+
+    /// Deserializing and serializing JSON of any format version should not change it.
+    #[test]
+    fn serialize_deserialize_round_trips(crate_ in arbitrary::crate_()) {
+        let original = serde_json::to_string(&crate_).unwrap();
+
+        for &version in SUPPORTED_VERSIONS {
+            let migrated = migrate(&original, version);
+
+            // Migrating to the same format version only deserializes and then serializes the
+            // JSON.
+            let round_tripped = migrate(&migrated, version);
+
+            prop_assert_eq!(parse(&migrated), parse(&round_tripped), "v{} did not round-trip", version);
+        }
+    }
+
+    /// Migrating across hops with identical schemas should not lose any data.
+    #[test]
+    fn unchanged_migrations_are_lossless(crate_ in arbitrary::crate_()) {
+        let original = serde_json::to_string(&crate_).unwrap();
+
+        for (from, to) in LOSSLESS_HOPS {
+            let before = migrate(&original, from);
+            let after = parse(&migrate(&before, to));
+
+            let mut expected = parse(&before);
+            expected["format_version"] = Value::from(to);
+
+            prop_assert_eq!(expected, after, "v{} to v{} was not lossless", from, to);
+        }
+    }
+
+    /// Sorted, pretty-printed output should contain the same JSON, with `index` and `paths`
+    /// ordered by `Id`.
+    #[test]
+    fn sorted_output_is_ordered_by_id(crate_ in arbitrary::crate_()) {
+        let original = serde_json::to_string(&crate_).unwrap();
+        let options = OutputOptions {
+            indent: Some(4),
+            sort_keys: true,
+            ..Default::default()
+        };
+
+        for &version in SUPPORTED_VERSIONS {
+            let sorted = migrate_with_options(&original, version, &options);
+
+            prop_assert_eq!(parse(&sorted), parse(&migrate(&original, version)));
+
+            let IndexAndPaths { index, paths } = serde_json::from_str(&sorted).unwrap();
+
+            for (field, OrderedKeys(keys)) in [("index", index), ("paths", paths)] {
+                let ids: Vec<u64> = keys.iter().map(|id| id.parse().unwrap()).collect();
+
+                prop_assert!(ids.is_sorted(), "v{} `{}` is not sorted: {:?}", version, field, ids);
+            }
+        }
+    }
+
+    /// JSON without a `format_version`, or with a wrong one, should be compatible with the version
+    /// it was migrated to, and still be migratable.
+    #[test]
+    fn missing_format_version_is_detected(crate_ in arbitrary::crate_()) {
+        let original = serde_json::to_string(&crate_).unwrap();
+
+        for &version in SUPPORTED_VERSIONS {
+            let mut migrated = parse(&migrate(&original, version));
+            migrated.as_object_mut().unwrap().remove("format_version");
+
+            let compatible = migrations::compatible_versions(&migrated);
+
+            prop_assert!(compatible.contains(&version), "v{} is not in {:?}", version, compatible);
+
+            let detected = parse(&migrate(&migrated.to_string(), MAXIMUM_VERSION));
+
+            prop_assert_eq!(&detected["format_version"], &Value::from(MAXIMUM_VERSION));
+
+            // A wrong `format_version` is ignored the same way.
+            migrated["format_version"] = Value::from(0);
+
+            prop_assert_eq!(migrations::compatible_versions(&migrated), compatible);
+        }
+    }
+
+    /// Migrating a typed `Crate` should produce the same `Crate` as migrating its JSON.
+    #[test]
+    fn typed_migration_matches_json(crate_ in arbitrary::crate_()) {
+        let original = serde_json::to_string(&crate_).unwrap();
+
+        let mut reporter = Reporter::new(Verbosity::Quiet);
+        reporter.begin_migrating(41);
+
+        let migrated: rustdoc_types_53::Crate = crate_.migrate_to(&mut reporter);
+
+        prop_assert_eq!(
+            serde_json::to_value(&migrated).unwrap(),
+            parse(&migrate(&original, 53)),
+        );
+        prop_assert_eq!(reporter.versions(), SUPPORTED_VERSIONS);
+    }
+
+    /// Viewing a `Crate` should give the same result no matter which format version it was
+    /// migrated to.
+    #[test]
+    fn views_agree_across_versions(crate_ in arbitrary::crate_()) {
+        let original = serde_json::to_string(&crate_).unwrap();
+        let expected = view_items(&crate_);
+
+        for &version in SUPPORTED_VERSIONS {
+            let migrated = VersionedCrate::from_json(&migrate(&original, version)).unwrap();
+            let view = migrated.view();
+
+            prop_assert_eq!(view.format_version(), version);
+            prop_assert_eq!(view.root(), crate_.root.0);
+            prop_assert_eq!(&view_items(view), &expected, "v{} has a different view", version);
+        }
+    }
+
+    /// JSON of every format version should match the schema generated for it.
+    #[test]
+    fn migrated_json_matches_schema(crate_ in arbitrary::crate_()) {
+        let original = serde_json::to_string(&crate_).unwrap();
+
+        for &version in SUPPORTED_VERSIONS {
+            let migrated = parse(&migrate(&original, version));
+            let errors = schema::validate(&migrated, version).unwrap();
+
+            prop_assert!(errors.is_empty(), "v{} does not match its schema: {:?}", version, errors);
+        }
+    }
+}
@@ -0,0 +1,784 @@
+//! [`Strategy`]s that generate arbitrary, valid [`rustdoc_types_41`] values.
+//!
+//! v41 is the oldest supported format version, so values generated here can be migrated through
+//! every hop.
+//!
+//! Recursive types are generated by [`type_()`]. Every other strategy that (transitively) contains
+//! a [`Type`](types::Type) accepts a [`BoxedStrategy`] of the current recursion level, so that
+//! building a strategy never recurses infinitely.
+
+use std::path::PathBuf;
+
+use proptest::{collection::vec, option, prelude::*, sample::select, strategy::Union};
+use rustdoc_types_41 as types;
+
+type TypeStrategy = BoxedStrategy<types::Type>;
+
+/// Generates an arbitrary [`Crate`](types::Crate).
+///
+/// The generated `Crate` is internally consistent: the root is a module containing every other
+/// item, and every item's key in `index` matches its `id`.
+pub fn crate_() -> impl Strategy<Value = types::Crate> {
+    (
+        option::of("[0-9]\\.[0-9]{1,2}\\.[0-9]{1,2}"),
+        any::<bool>(),
+        item(),
+        vec(item(), 0..12),
+        vec(item_summary(), 0..8),
+        vec(external_crate(), 0..4),
+    )
+        .prop_map(
+            |(crate_version, includes_private, mut root, items, paths, external_crates)| {
+                let root_id = types::Id(0);
+
+                let items: Vec<_> = items
+                    .into_iter()
+                    .zip(1..)
+                    .map(|(mut item, id)| {
+                        item.id = types::Id(id);
+                        item.crate_id = 0;
+                        item
+                    })
+                    .collect();
+
+                root.id = root_id;
+                root.crate_id = 0;
+                root.inner = types::ItemEnum::Module(types::Module {
+                    is_crate: true,
+                    items: items.iter().map(|item| item.id).collect(),
+                    is_stripped: false,
+                });
+
+                types::Crate {
+                    root: root_id,
+                    crate_version,
+                    includes_private,
+                    index: std::iter::once(root)
+                        .chain(items)
+                        .map(|item| (item.id, item))
+                        .collect(),
+                    // Offset the `Id`s of external items, so they don't collide with local items.
+                    paths: paths
+                        .into_iter()
+                        .zip(1000..)
+                        .map(|(summary, id)| (types::Id(id), summary))
+                        .collect(),
+                    external_crates: external_crates
+                        .into_iter()
+                        .zip(1..)
+                        .map(|(c, i)| (i, c))
+                        .collect(),
+                    format_version: 41,
+                }
+            },
+        )
+}
+
+fn item() -> impl Strategy<Value = types::Item> {
+    (
+        option::of(ident()),
+        option::of(span()),
+        visibility(),
+        option::of(docs()),
+        vec((ident(), id()), 0..3),
+        vec(attr(), 0..3),
+        option::of(deprecation()),
+        item_enum(),
+    )
+        .prop_map(
+            |(name, span, visibility, docs, links, attrs, deprecation, inner)| types::Item {
+                // The `Id`s are overwritten by `crate_()`.
+                id: types::Id(0),
+                crate_id: 0,
+                name,
+                span,
+                visibility,
+                docs,
+                links: links.into_iter().collect(),
+                attrs,
+                deprecation,
+                inner,
+            },
+        )
+}
+
+fn item_enum() -> impl Strategy<Value = types::ItemEnum> {
+    let ty = type_();
+
+    Union::new([
+        module().prop_map(types::ItemEnum::Module).boxed(),
+        (ident(), option::of(ident()))
+            .prop_map(|(name, rename)| types::ItemEnum::ExternCrate { name, rename })
+            .boxed(),
+        use_().prop_map(types::ItemEnum::Use).boxed(),
+        union_(ty.clone()).prop_map(types::ItemEnum::Union).boxed(),
+        struct_(ty.clone())
+            .prop_map(types::ItemEnum::Struct)
+            .boxed(),
+        ty.clone().prop_map(types::ItemEnum::StructField).boxed(),
+        enum_(ty.clone()).prop_map(types::ItemEnum::Enum).boxed(),
+        variant().prop_map(types::ItemEnum::Variant).boxed(),
+        function(ty.clone())
+            .prop_map(types::ItemEnum::Function)
+            .boxed(),
+        trait_(ty.clone()).prop_map(types::ItemEnum::Trait).boxed(),
+        (generics(ty.clone()), vec(generic_bound(ty.clone()), 0..3))
+            .prop_map(|(generics, params)| {
+                types::ItemEnum::TraitAlias(types::TraitAlias { generics, params })
+            })
+            .boxed(),
+        impl_(ty.clone()).prop_map(types::ItemEnum::Impl).boxed(),
+        (ty.clone(), generics(ty.clone()))
+            .prop_map(|(type_, generics)| {
+                types::ItemEnum::TypeAlias(types::TypeAlias { type_, generics })
+            })
+            .boxed(),
+        (ty.clone(), constant())
+            .prop_map(|(type_, const_)| types::ItemEnum::Constant { type_, const_ })
+            .boxed(),
+        (ty.clone(), any::<bool>(), expr(), any::<bool>())
+            .prop_map(|(type_, is_mutable, expr, is_unsafe)| {
+                types::ItemEnum::Static(types::Static {
+                    type_,
+                    is_mutable,
+                    expr,
+                    is_unsafe,
+                })
+            })
+            .boxed(),
+        Just(types::ItemEnum::ExternType).boxed(),
+        "macro_rules! [a-z]{1,8} \\{ \\}"
+            .prop_map(types::ItemEnum::Macro)
+            .boxed(),
+        (
+            select(vec![
+                types::MacroKind::Bang,
+                types::MacroKind::Attr,
+                types::MacroKind::Derive,
+            ]),
+            vec(ident(), 0..3),
+        )
+            .prop_map(|(kind, helpers)| {
+                types::ItemEnum::ProcMacro(types::ProcMacro { kind, helpers })
+            })
+            .boxed(),
+        (primitive_name(), vec(id(), 0..3))
+            .prop_map(|(name, impls)| types::ItemEnum::Primitive(types::Primitive { name, impls }))
+            .boxed(),
+        (ty.clone(), option::of(expr()))
+            .prop_map(|(type_, value)| types::ItemEnum::AssocConst { type_, value })
+            .boxed(),
+        (
+            generics(ty.clone()),
+            vec(generic_bound(ty.clone()), 0..3),
+            option::of(ty),
+        )
+            .prop_map(|(generics, bounds, type_)| types::ItemEnum::AssocType {
+                generics,
+                bounds,
+                type_,
+            })
+            .boxed(),
+    ])
+}
+
+fn module() -> impl Strategy<Value = types::Module> {
+    (vec(id(), 0..4), any::<bool>()).prop_map(|(items, is_stripped)| types::Module {
+        is_crate: false,
+        items,
+        is_stripped,
+    })
+}
+
+fn use_() -> impl Strategy<Value = types::Use> {
+    (
+        "[a-z]{1,8}(::[a-z]{1,8}){0,2}",
+        ident(),
+        option::of(id()),
+        any::<bool>(),
+    )
+        .prop_map(|(source, name, id, is_glob)| types::Use {
+            source,
+            name,
+            id,
+            is_glob,
+        })
+}
+
+fn union_(ty: TypeStrategy) -> impl Strategy<Value = types::Union> {
+    (
+        generics(ty),
+        any::<bool>(),
+        vec(id(), 0..4),
+        vec(id(), 0..4),
+    )
+        .prop_map(
+            |(generics, has_stripped_fields, fields, impls)| types::Union {
+                generics,
+                has_stripped_fields,
+                fields,
+                impls,
+            },
+        )
+}
+
+fn struct_(ty: TypeStrategy) -> impl Strategy<Value = types::Struct> {
+    let kind = prop_oneof![
+        Just(types::StructKind::Unit),
+        vec(option::of(id()), 0..4).prop_map(types::StructKind::Tuple),
+        (vec(id(), 0..4), any::<bool>()).prop_map(|(fields, has_stripped_fields)| {
+            types::StructKind::Plain {
+                fields,
+                has_stripped_fields,
+            }
+        }),
+    ];
+
+    (kind, generics(ty), vec(id(), 0..4)).prop_map(|(kind, generics, impls)| types::Struct {
+        kind,
+        generics,
+        impls,
+    })
+}
+
+fn enum_(ty: TypeStrategy) -> impl Strategy<Value = types::Enum> {
+    (
+        generics(ty),
+        any::<bool>(),
+        vec(id(), 0..4),
+        vec(id(), 0..4),
+    )
+        .prop_map(
+            |(generics, has_stripped_variants, variants, impls)| types::Enum {
+                generics,
+                has_stripped_variants,
+                variants,
+                impls,
+            },
+        )
+}
+
+fn variant() -> impl Strategy<Value = types::Variant> {
+    let kind = prop_oneof![
+        Just(types::VariantKind::Plain),
+        vec(option::of(id()), 0..4).prop_map(types::VariantKind::Tuple),
+        (vec(id(), 0..4), any::<bool>()).prop_map(|(fields, has_stripped_fields)| {
+            types::VariantKind::Struct {
+                fields,
+                has_stripped_fields,
+            }
+        }),
+    ];
+
+    let discriminant =
+        (expr(), "-?[0-9]{1,4}").prop_map(|(expr, value)| types::Discriminant { expr, value });
+
+    (kind, option::of(discriminant))
+        .prop_map(|(kind, discriminant)| types::Variant { kind, discriminant })
+}
+
+fn function(ty: TypeStrategy) -> impl Strategy<Value = types::Function> {
+    (
+        function_signature(ty.clone()),
+        generics(ty),
+        function_header(),
+        any::<bool>(),
+    )
+        .prop_map(|(sig, generics, header, has_body)| types::Function {
+            sig,
+            generics,
+            header,
+            has_body,
+        })
+}
+
+fn trait_(ty: TypeStrategy) -> impl Strategy<Value = types::Trait> {
+    (
+        any::<bool>(),
+        any::<bool>(),
+        any::<bool>(),
+        vec(id(), 0..4),
+        generics(ty.clone()),
+        vec(generic_bound(ty), 0..3),
+        vec(id(), 0..4),
+    )
+        .prop_map(
+            |(is_auto, is_unsafe, is_dyn_compatible, items, generics, bounds, implementations)| {
+                types::Trait {
+                    is_auto,
+                    is_unsafe,
+                    is_dyn_compatible,
+                    items,
+                    generics,
+                    bounds,
+                    implementations,
+                }
+            },
+        )
+}
+
+fn impl_(ty: TypeStrategy) -> impl Strategy<Value = types::Impl> {
+    (
+        any::<bool>(),
+        generics(ty.clone()),
+        vec(ident(), 0..3),
+        option::of(path(ty.clone())),
+        ty.clone(),
+        vec(id(), 0..4),
+        any::<bool>(),
+        any::<bool>(),
+        option::of(ty),
+    )
+        .prop_map(
+            |(
+                is_unsafe,
+                generics,
+                provided_trait_methods,
+                trait_,
+                for_,
+                items,
+                is_negative,
+                is_synthetic,
+                blanket_impl,
+            )| types::Impl {
+                is_unsafe,
+                generics,
+                provided_trait_methods,
+                trait_,
+                for_,
+                items,
+                is_negative,
+                is_synthetic,
+                blanket_impl,
+            },
+        )
+}
+
+/// Generates an arbitrary, possibly nested, [`Type`](types::Type).
+fn type_() -> TypeStrategy {
+    let leaf = prop_oneof![
+        ident().prop_map(types::Type::Generic),
+        primitive_name().prop_map(types::Type::Primitive),
+        Just(types::Type::Infer),
+    ];
+
+    leaf.prop_recursive(3, 24, 4, |inner| {
+        Union::new([
+            path(inner.clone())
+                .prop_map(types::Type::ResolvedPath)
+                .boxed(),
+            (vec(poly_trait(inner.clone()), 1..3), option::of(lifetime()))
+                .prop_map(|(traits, lifetime)| {
+                    types::Type::DynTrait(types::DynTrait { traits, lifetime })
+                })
+                .boxed(),
+            (
+                function_signature(inner.clone()),
+                vec(lifetime_param_def(), 0..2),
+                function_header(),
+            )
+                .prop_map(|(sig, generic_params, header)| {
+                    types::Type::FunctionPointer(Box::new(types::FunctionPointer {
+                        sig,
+                        generic_params,
+                        header,
+                    }))
+                })
+                .boxed(),
+            vec(inner.clone(), 0..3)
+                .prop_map(types::Type::Tuple)
+                .boxed(),
+            inner
+                .clone()
+                .prop_map(|type_| types::Type::Slice(Box::new(type_)))
+                .boxed(),
+            (inner.clone(), "[0-9]{1,3}")
+                .prop_map(|(type_, len)| types::Type::Array {
+                    type_: Box::new(type_),
+                    len,
+                })
+                .boxed(),
+            (inner.clone(), "[0-9]{1,3}\\.\\.=[0-9]{1,3}")
+                .prop_map(|(type_, pat)| types::Type::Pat {
+                    type_: Box::new(type_),
+                    __pat_unstable_do_not_use: pat,
+                })
+                .boxed(),
+            vec(generic_bound(inner.clone()), 1..3)
+                .prop_map(types::Type::ImplTrait)
+                .boxed(),
+            (any::<bool>(), inner.clone())
+                .prop_map(|(is_mutable, type_)| types::Type::RawPointer {
+                    is_mutable,
+                    type_: Box::new(type_),
+                })
+                .boxed(),
+            (option::of(lifetime()), any::<bool>(), inner.clone())
+                .prop_map(|(lifetime, is_mutable, type_)| types::Type::BorrowedRef {
+                    lifetime,
+                    is_mutable,
+                    type_: Box::new(type_),
+                })
+                .boxed(),
+            (
+                ident(),
+                generic_args(inner.clone()),
+                inner.clone(),
+                option::of(path(inner)),
+            )
+                .prop_map(
+                    |(name, args, self_type, trait_)| types::Type::QualifiedPath {
+                        name,
+                        args: Box::new(args),
+                        self_type: Box::new(self_type),
+                        trait_,
+                    },
+                )
+                .boxed(),
+        ])
+    })
+    .boxed()
+}
+
+fn path(ty: TypeStrategy) -> impl Strategy<Value = types::Path> {
+    (
+        "[A-Z][a-z]{0,8}",
+        id(),
+        option::of(generic_args(ty).prop_map(Box::new)),
+    )
+        .prop_map(|(path, id, args)| types::Path { path, id, args })
+}
+
+fn poly_trait(ty: TypeStrategy) -> impl Strategy<Value = types::PolyTrait> {
+    (path(ty), vec(lifetime_param_def(), 0..2)).prop_map(|(trait_, generic_params)| {
+        types::PolyTrait {
+            trait_,
+            generic_params,
+        }
+    })
+}
+
+fn generic_args(ty: TypeStrategy) -> impl Strategy<Value = types::GenericArgs> {
+    prop_oneof![
+        (
+            vec(generic_arg(ty.clone()), 0..3),
+            vec(assoc_item_constraint(ty.clone()), 0..2),
+        )
+            .prop_map(|(args, constraints)| types::GenericArgs::AngleBracketed {
+                args,
+                constraints,
+            }),
+        (vec(ty.clone(), 0..3), option::of(ty))
+            .prop_map(|(inputs, output)| types::GenericArgs::Parenthesized { inputs, output }),
+    ]
+}
+
+/// Like [`generic_args()`], but never generates [`AssocItemConstraint`](types::AssocItemConstraint)s.
+///
+/// This is used to prevent [`assoc_item_constraint()`] from recursing infinitely.
+fn generic_args_without_constraints(ty: TypeStrategy) -> impl Strategy<Value = types::GenericArgs> {
+    vec(generic_arg(ty), 0..3).prop_map(|args| types::GenericArgs::AngleBracketed {
+        args,
+        constraints: Vec::new(),
+    })
+}
+
+fn generic_arg(ty: TypeStrategy) -> impl Strategy<Value = types::GenericArg> {
+    prop_oneof![
+        lifetime().prop_map(types::GenericArg::Lifetime),
+        ty.prop_map(types::GenericArg::Type),
+        constant().prop_map(types::GenericArg::Const),
+        Just(types::GenericArg::Infer),
+    ]
+}
+
+fn assoc_item_constraint(ty: TypeStrategy) -> impl Strategy<Value = types::AssocItemConstraint> {
+    let binding = prop_oneof![
+        term(ty.clone()).prop_map(types::AssocItemConstraintKind::Equality),
+        vec(lifetime().prop_map(types::GenericBound::Outlives), 1..3)
+            .prop_map(types::AssocItemConstraintKind::Constraint),
+    ];
+
+    (ident(), generic_args_without_constraints(ty), binding).prop_map(|(name, args, binding)| {
+        types::AssocItemConstraint {
+            name,
+            args,
+            binding,
+        }
+    })
+}
+
+fn term(ty: TypeStrategy) -> impl Strategy<Value = types::Term> {
+    prop_oneof![
+        ty.prop_map(types::Term::Type),
+        constant().prop_map(types::Term::Constant),
+    ]
+}
+
+fn generic_bound(ty: TypeStrategy) -> impl Strategy<Value = types::GenericBound> {
+    let modifier = select(vec![
+        types::TraitBoundModifier::None,
+        types::TraitBoundModifier::Maybe,
+        types::TraitBoundModifier::MaybeConst,
+    ]);
+
+    let precise_capturing_arg = prop_oneof![
+        lifetime().prop_map(types::PreciseCapturingArg::Lifetime),
+        ident().prop_map(types::PreciseCapturingArg::Param),
+    ];
+
+    prop_oneof![
+        (path(ty), vec(lifetime_param_def(), 0..2), modifier).prop_map(
+            |(trait_, generic_params, modifier)| types::GenericBound::TraitBound {
+                trait_,
+                generic_params,
+                modifier,
+            }
+        ),
+        lifetime().prop_map(types::GenericBound::Outlives),
+        vec(precise_capturing_arg, 0..3).prop_map(types::GenericBound::Use),
+    ]
+}
+
+fn generics(ty: TypeStrategy) -> impl Strategy<Value = types::Generics> {
+    (
+        vec(generic_param_def(ty.clone()), 0..3),
+        vec(where_predicate(ty), 0..2),
+    )
+        .prop_map(|(params, where_predicates)| types::Generics {
+            params,
+            where_predicates,
+        })
+}
+
+fn generic_param_def(ty: TypeStrategy) -> impl Strategy<Value = types::GenericParamDef> {
+    let kind = prop_oneof![
+        vec(lifetime(), 0..2)
+            .prop_map(|outlives| types::GenericParamDefKind::Lifetime { outlives }),
+        (
+            vec(generic_bound(ty.clone()), 0..2),
+            option::of(ty.clone()),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(bounds, default, is_synthetic)| types::GenericParamDefKind::Type {
+                    bounds,
+                    default,
+                    is_synthetic,
+                }
+            ),
+        (ty, option::of(expr()))
+            .prop_map(|(type_, default)| types::GenericParamDefKind::Const { type_, default }),
+    ];
+
+    (ident(), kind).prop_map(|(name, kind)| types::GenericParamDef { name, kind })
+}
+
+/// Generates a lifetime [`GenericParamDef`](types::GenericParamDef), such as those found in
+/// higher-ranked trait bounds.
+fn lifetime_param_def() -> impl Strategy<Value = types::GenericParamDef> {
+    (lifetime(), vec(lifetime(), 0..2)).prop_map(|(name, outlives)| types::GenericParamDef {
+        name,
+        kind: types::GenericParamDefKind::Lifetime { outlives },
+    })
+}
+
+fn where_predicate(ty: TypeStrategy) -> impl Strategy<Value = types::WherePredicate> {
+    prop_oneof![
+        (
+            ty.clone(),
+            vec(generic_bound(ty.clone()), 1..3),
+            vec(lifetime_param_def(), 0..2),
+        )
+            .prop_map(|(type_, bounds, generic_params)| {
+                types::WherePredicate::BoundPredicate {
+                    type_,
+                    bounds,
+                    generic_params,
+                }
+            }),
+        (lifetime(), vec(lifetime(), 1..3)).prop_map(|(lifetime, outlives)| {
+            types::WherePredicate::LifetimePredicate { lifetime, outlives }
+        }),
+        (ty.clone(), term(ty))
+            .prop_map(|(lhs, rhs)| types::WherePredicate::EqPredicate { lhs, rhs }),
+    ]
+}
+
+fn function_signature(ty: TypeStrategy) -> impl Strategy<Value = types::FunctionSignature> {
+    (
+        vec((ident(), ty.clone()), 0..3),
+        option::of(ty),
+        any::<bool>(),
+    )
+        .prop_map(|(inputs, output, is_c_variadic)| types::FunctionSignature {
+            inputs,
+            output,
+            is_c_variadic,
+        })
+}
+
+fn function_header() -> impl Strategy<Value = types::FunctionHeader> {
+    (any::<bool>(), any::<bool>(), any::<bool>(), abi()).prop_map(
+        |(is_const, is_unsafe, is_async, abi)| types::FunctionHeader {
+            is_const,
+            is_unsafe,
+            is_async,
+            abi,
+        },
+    )
+}
+
+fn abi() -> impl Strategy<Value = types::Abi> {
+    prop_oneof![
+        Just(types::Abi::Rust),
+        any::<bool>().prop_map(|unwind| types::Abi::C { unwind }),
+        any::<bool>().prop_map(|unwind| types::Abi::Cdecl { unwind }),
+        any::<bool>().prop_map(|unwind| types::Abi::Stdcall { unwind }),
+        any::<bool>().prop_map(|unwind| types::Abi::Fastcall { unwind }),
+        any::<bool>().prop_map(|unwind| types::Abi::Aapcs { unwind }),
+        any::<bool>().prop_map(|unwind| types::Abi::Win64 { unwind }),
+        any::<bool>().prop_map(|unwind| types::Abi::SysV64 { unwind }),
+        any::<bool>().prop_map(|unwind| types::Abi::System { unwind }),
+        "[a-z\\-]{1,12}".prop_map(types::Abi::Other),
+    ]
+}
+
+fn constant() -> impl Strategy<Value = types::Constant> {
+    (expr(), option::of("[0-9]{1,4}"), any::<bool>()).prop_map(|(expr, value, is_literal)| {
+        types::Constant {
+            expr,
+            value,
+            is_literal,
+        }
+    })
+}
+
+fn item_summary() -> impl Strategy<Value = types::ItemSummary> {
+    let kind = select(vec![
+        types::ItemKind::Module,
+        types::ItemKind::ExternCrate,
+        types::ItemKind::Use,
+        types::ItemKind::Struct,
+        types::ItemKind::StructField,
+        types::ItemKind::Union,
+        types::ItemKind::Enum,
+        types::ItemKind::Variant,
+        types::ItemKind::Function,
+        types::ItemKind::TypeAlias,
+        types::ItemKind::Constant,
+        types::ItemKind::Trait,
+        types::ItemKind::TraitAlias,
+        types::ItemKind::Impl,
+        types::ItemKind::Static,
+        types::ItemKind::ExternType,
+        types::ItemKind::Macro,
+        types::ItemKind::ProcAttribute,
+        types::ItemKind::ProcDerive,
+        types::ItemKind::AssocConst,
+        types::ItemKind::AssocType,
+        types::ItemKind::Primitive,
+        types::ItemKind::Keyword,
+    ]);
+
+    (1..8u32, vec(ident(), 1..4), kind).prop_map(|(crate_id, path, kind)| types::ItemSummary {
+        crate_id,
+        path,
+        kind,
+    })
+}
+
+fn external_crate() -> impl Strategy<Value = types::ExternalCrate> {
+    (ident(), option::of("https://docs\\.rs/[a-z]{1,8}/latest/")).prop_map(
+        |(name, html_root_url)| types::ExternalCrate {
+            name,
+            html_root_url,
+        },
+    )
+}
+
+fn span() -> impl Strategy<Value = types::Span> {
+    (
+        "src/[a-z]{1,8}\\.rs",
+        (1..1000usize, 0..100usize),
+        (1..1000usize, 0..100usize),
+    )
+        .prop_map(|(filename, begin, end)| types::Span {
+            filename: PathBuf::from(filename),
+            begin,
+            end,
+        })
+}
+
+fn visibility() -> impl Strategy<Value = types::Visibility> {
+    prop_oneof![
+        Just(types::Visibility::Public),
+        Just(types::Visibility::Default),
+        Just(types::Visibility::Crate),
+        (id(), "crate(::[a-z]{1,8}){0,2}")
+            .prop_map(|(parent, path)| types::Visibility::Restricted { parent, path }),
+    ]
+}
+
+fn deprecation() -> impl Strategy<Value = types::Deprecation> {
+    (option::of("[0-9]\\.[0-9]{1,2}\\.0"), option::of(docs()))
+        .prop_map(|(since, note)| types::Deprecation { since, note })
+}
+
+/// Generates an attribute in the form Rustdoc v41 would emit it.
+///
+/// This includes attributes whose format later changes, such as `#[inline]` and `#[must_use]`, so
+/// that their migrations are exercised as well.
+fn attr() -> impl Strategy<Value = String> {
+    prop_oneof![
+        select(vec![
+            "#[attr = Repr([ReprC])]\n",
+            "#[attr = Repr([ReprTransparent])]\n",
+            "#[attr = Repr([ReprInt(UnsignedInt(U8)), ReprC])]\n",
+            "#[attr = Repr([ReprRust, ReprPacked(Align(2 bytes))])]\n",
+            "#[inline]",
+            "#[inline(always)]",
+            "#[inline(never)]",
+            "#[optimize(none)]",
+            "#[optimize(speed)]",
+            "#[optimize(size)]",
+            "#[cold]",
+            "#[must_use]",
+            "#[non_exhaustive]",
+            "#[doc(hidden)]",
+            "#[no_mangle]",
+        ])
+        .prop_map(String::from),
+        "[a-z ]{0,16}".prop_map(|message| format!("#[must_use = \"{message}\"]")),
+        ident().prop_map(|name| format!("#[export_name = \"{name}\"]")),
+    ]
+}
+
+fn id() -> impl Strategy<Value = types::Id> {
+    (0..64u32).prop_map(types::Id)
+}
+
+fn ident() -> impl Strategy<Value = String> {
+    "[a-zA-Z_][a-zA-Z0-9_]{0,8}"
+}
+
+fn lifetime() -> impl Strategy<Value = String> {
+    "'[a-z]{1,3}"
+}
+
+fn primitive_name() -> impl Strategy<Value = String> {
+    select(vec![
+        "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+        "i64", "i128", "isize", "f32", "f64",
+    ])
+    .prop_map(String::from)
+}
+
+fn expr() -> impl Strategy<Value = String> {
+    "[0-9]{1,4}|_|\\{ [a-z]{1,8} \\}"
+}
+
+/// Generates documentation, including characters that must be escaped in JSON.
+fn docs() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9 \"\\\\\n\t`*#]{0,32}"
+}
@@ -0,0 +1,201 @@
+//! Tests that the generated JSON Schemas describe Rustdoc JSON, and that validating against them
+//! reports the path to each invalid value.
+
+mod harness;
+
+use migrate_rustdoc_json::{
+    migrations::MAXIMUM_VERSION,
+    schema::{self, Change, Member, MemberDiff, TypeDiff, ValidationError},
+};
+use serde_json::{Value, json};
+
+use self::harness::fixture::{item, minimal_crate, unit_struct};
+
+/// Returns the JSON of a v53 crate whose root module contains a unit struct.
+fn crate_() -> Value {
+    minimal_crate(53, [item(1, "Unit", unit_struct())])
+}
+
+fn validate(json: &Value) -> Vec<ValidationError> {
+    schema::validate(json, 53).unwrap()
+}
+
+#[test]
+fn schema_refers_to_crate() {
+    let schema = schema::schema(MAXIMUM_VERSION).unwrap();
+
+    assert_eq!(schema["$ref"], "#/$defs/Crate");
+    assert_eq!(
+        schema["$defs"]["Crate"]["properties"]["format_version"]["const"],
+        MAXIMUM_VERSION,
+    );
+
+    // `Id` is a newtype, so it is (de)serialized as an integer.
+    assert_eq!(schema["$defs"]["Id"]["type"], "integer");
+}
+
+#[test]
+fn valid_crate() {
+    let errors = validate(&crate_());
+
+    assert!(errors.is_empty(), "{errors:?}");
+}
+
+#[test]
+fn unknown_variant() {
+    let mut crate_ = crate_();
+    crate_["index"]["1"]["inner"] = json!({ "from_the_future": {} });
+
+    let errors = validate(&crate_);
+
+    let [error] = &errors[..] else {
+        panic!("expected one error, got {errors:?}");
+    };
+
+    assert_eq!(error.path, "/index/1/inner");
+    assert!(
+        error.message.contains("unknown variant `from_the_future`"),
+        "{error}"
+    );
+}
+
+#[test]
+fn nested_type_error() {
+    let mut crate_ = crate_();
+    crate_["index"]["1"]["inner"]["struct"]["kind"] = json!({ "tuple": [null, "x"] });
+
+    assert_eq!(
+        validate(&crate_),
+        [ValidationError {
+            path: "/index/1/inner/struct/kind/tuple/1".to_owned(),
+            message: "expected an integer, found the string \"x\"".to_owned(),
+        }],
+    );
+}
+
+#[test]
+fn missing_field() {
+    let mut crate_ = crate_();
+    crate_["index"]["1"]
+        .as_object_mut()
+        .unwrap()
+        .remove("visibility");
+
+    assert_eq!(
+        validate(&crate_),
+        [ValidationError {
+            path: "/index/1".to_owned(),
+            message: "missing field `visibility`".to_owned(),
+        }],
+    );
+}
+
+#[test]
+fn every_error_is_reported() {
+    let mut crate_ = crate_();
+    crate_["index"]["0"]["name"] = json!(5);
+    crate_["index"]["1"]["span"]["begin"] = json!([1]);
+
+    let paths: Vec<String> = validate(&crate_)
+        .into_iter()
+        .map(|error| error.path)
+        .collect();
+
+    assert_eq!(paths, ["/index/0/name", "/index/1/span/begin"]);
+}
+
+#[test]
+fn format_version_must_match() {
+    let errors = schema::validate(&crate_(), 52).unwrap();
+
+    assert_eq!(
+        errors,
+        [ValidationError {
+            path: "/format_version".to_owned(),
+            message: "expected `52`, found the integer `53`".to_owned(),
+        }],
+    );
+}
+
+#[test]
+fn unsupported_version() {
+    assert!(schema::schema(47).is_err());
+}
+
+#[test]
+fn diff_of_identical_schemas() {
+    // v53 was released by mistake, without any changes to the types.
+    let diff = schema::diff(52, 53).unwrap();
+
+    assert!(diff.types.is_empty(), "{diff}");
+}
+
+#[test]
+fn diff_reports_added_variants() {
+    let diff = schema::diff(41, 42).unwrap();
+
+    assert_eq!(
+        diff.types,
+        [TypeDiff {
+            name: "GenericArgs".to_owned(),
+            change: None,
+            members: vec![MemberDiff {
+                member: Member::Variant("return_type_notation".to_owned()),
+                change: Change::Added("unit".to_owned()),
+            }],
+        }],
+    );
+
+    let reverse = schema::diff(42, 41).unwrap();
+
+    assert_eq!(
+        reverse.types[0].members[0].change,
+        Change::Removed("unit".to_owned()),
+    );
+}
+
+#[test]
+fn diff_reports_changed_fields() {
+    let diff = schema::diff(50, 51).unwrap();
+
+    let changed = Change::Changed {
+        from: "GenericArgs".to_owned(),
+        to: "Option<GenericArgs>".to_owned(),
+    };
+
+    assert_eq!(
+        diff.types,
+        [
+            TypeDiff {
+                name: "AssocItemConstraint".to_owned(),
+                change: None,
+                members: vec![MemberDiff {
+                    member: Member::Field("args".to_owned()),
+                    change: changed.clone(),
+                }],
+            },
+            TypeDiff {
+                name: "Type".to_owned(),
+                change: None,
+                members: vec![MemberDiff {
+                    member: Member::VariantField {
+                        variant: "qualified_path".to_owned(),
+                        field: "args".to_owned(),
+                    },
+                    change: changed,
+                }],
+            },
+        ],
+    );
+
+    assert_eq!(
+        diff.to_string(),
+        "\
+Changes from v50 to v51:
+~ `AssocItemConstraint`
+    ~ field `args`: `GenericArgs` to `Option<GenericArgs>`
+~ `Type`
+    ~ field `args` of variant `qualified_path`: `GenericArgs` to `Option<GenericArgs>`
+",
+    );
+}
@@ -0,0 +1,42 @@
+//! Tests that the `rustdoc_types` sources vendored for `impl_unchanged_migrations!` match the
+//! versions `migrate_rustdoc_json` depends on.
+
+use std::{fs, path::Path};
+
+#[test]
+fn vendored_sources_match_dependencies() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+
+    let dependencies: Vec<(&str, &str)> = manifest
+        .lines()
+        .filter_map(|line| {
+            let (extern_name, rest) = line.split_once(" = { version = \"=")?;
+            let (version, _) = rest.split_once('"')?;
+
+            extern_name
+                .starts_with("rustdoc_types_")
+                .then_some((extern_name, version))
+        })
+        .collect();
+
+    assert!(!dependencies.is_empty(), "no pinned `rustdoc_types` found");
+
+    let sources = root.join("macros/sources");
+
+    for (extern_name, version) in &dependencies {
+        let path = sources.join(format!("{extern_name}.rs"));
+
+        let source = fs::read_to_string(&path)
+            .unwrap_or_else(|error| panic!("could not read `{}`: {error}", path.display()));
+
+        assert!(
+            source.starts_with(&format!("// Vendored from `rustdoc-types {version}`,")),
+            "`{}` was not vendored from `rustdoc-types {version}`",
+            path.display(),
+        );
+    }
+
+    // Every vendored source should be used.
+    assert_eq!(fs::read_dir(&sources).unwrap().count(), dependencies.len());
+}
@@ -0,0 +1,88 @@
+//! Tests that [`CrateView`](migrate_rustdoc_json::view::CrateView) normalizes values that changed representation between format versions.
+
+mod harness;
+
+use migrate_rustdoc_json::{
+    migrations::VersionedCrate,
+    view::{ItemKind, Visibility},
+};
+use serde_json::json;
+
+use self::harness::fixture::minimal_crate;
+
+/// Returns the JSON of a crate whose root module spans lines 1 to 10, beginning and ending at
+/// `column`, and has the given attributes.
+fn crate_json(format_version: u32, column: usize, attrs: &[&str]) -> String {
+    let mut crate_ = minimal_crate(format_version, []);
+
+    crate_["crate_version"] = json!("1.0.0");
+
+    let root = &mut crate_["index"]["0"];
+
+    root["span"] = json!({ "filename": "src/lib.rs", "begin": [1, column], "end": [10, column] });
+    root["docs"] = json!("The crate root.");
+    root["attrs"] = json!(attrs);
+
+    crate_.to_string()
+}
+
+#[test]
+fn span_columns_begin_at_one() {
+    // Columns began at 0 before v45.
+    let old = VersionedCrate::from_json(&crate_json(44, 0, &[])).unwrap();
+    let new = VersionedCrate::from_json(&crate_json(45, 1, &[])).unwrap();
+
+    let old_span = old.view().item(0).unwrap().span().unwrap();
+    let new_span = new.view().item(0).unwrap().span().unwrap();
+
+    assert_eq!(old_span.begin, (1, 1));
+    assert_eq!(old_span.end, (10, 1));
+    assert_eq!(old_span, new_span);
+}
+
+#[test]
+fn attrs_use_newest_form() {
+    let crate_ = VersionedCrate::from_json(&crate_json(
+        46,
+        1,
+        &[
+            "#[inline]",
+            "#[cold]",
+            "#[must_use = \"hello\"]",
+            "#[doc(hidden)]",
+        ],
+    ))
+    .unwrap();
+
+    assert_eq!(
+        crate_.view().item(0).unwrap().attrs(),
+        [
+            "#[attr = Inline(Hint)]",
+            "#[attr = Cold]",
+            "#[attr = MustUse {reason: \"hello\"}]",
+            "#[doc(hidden)]",
+        ],
+    );
+}
+
+#[test]
+fn crate_and_items() {
+    let crate_ = VersionedCrate::from_json(&crate_json(53, 1, &[])).unwrap();
+    let view = crate_.view();
+
+    assert_eq!(view.format_version(), 53);
+    assert_eq!(view.crate_version(), Some("1.0.0"));
+    assert_eq!(view.items().count(), 1);
+
+    let root = view.item(view.root()).unwrap();
+
+    assert_eq!(root.name(), Some("my_crate"));
+    assert_eq!(root.docs(), Some("The crate root."));
+    assert_eq!(root.kind(), ItemKind::Module);
+    assert_eq!(root.visibility(), Visibility::Public);
+
+    let path = view.path(0).unwrap();
+
+    assert_eq!(path.path, ["my_crate"]);
+    assert_eq!(path.kind, ItemKind::Module);
+}
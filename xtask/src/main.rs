@@ -0,0 +1,77 @@
+//! Developer tasks for `migrate_rustdoc_json`, run with `cargo xtask <TASK>`.
+
+mod metadata;
+mod new_migration;
+
+// This module is shared with `migrate_rustdoc_json_macros`. Proc-macro crates cannot export
+// anything besides macros, so it is included by path instead.
+#[path = "../../macros/src/shape.rs"]
+mod shape;
+
+use std::{ffi::OsStr, path::Path};
+
+use anyhow::Context;
+
+fn main() -> anyhow::Result<()> {
+    let mut args = pico_args::Arguments::from_env();
+
+    if args.contains(["-h", "--help"]) {
+        print_help();
+        return Ok(());
+    }
+
+    let Some(task) = args.subcommand()? else {
+        print_help();
+        return Ok(());
+    };
+
+    match task.as_str() {
+        "new-migration" => {
+            let to_version: u32 = args
+                .free_from_str()
+                .context("expected the new format version, such as `54`")?;
+            let rustdoc_types_version: String = args
+                .free_from_str()
+                .context("expected the `rustdoc-types` version, such as `0.54`")?;
+
+            finish(args)?;
+
+            new_migration::new_migration(workspace_root(), to_version, &rustdoc_types_version)
+        }
+        task => Err(anyhow::anyhow!(
+            "unknown task `{task}`, run `cargo xtask --help` for a list of tasks"
+        )),
+    }
+}
+
+/// Errors if any unused arguments remain.
+fn finish(args: pico_args::Arguments) -> anyhow::Result<()> {
+    let remaining = args.finish();
+
+    anyhow::ensure!(
+        remaining.is_empty(),
+        "unsupported arguments were passed: {}",
+        remaining.join(OsStr::new(", ")).to_string_lossy()
+    );
+
+    Ok(())
+}
+
+/// Returns the path to the root of the workspace, which contains `migrate_rustdoc_json`.
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+fn print_help() {
+    println!(
+        "\
+Developer tasks for migrate_rustdoc_json
+
+Usage: cargo xtask <TASK>
+
+Tasks:
+  new-migration <FORMAT_VERSION> <RUSTDOC_TYPES_VERSION>
+      Scaffolds the migration from the newest supported format version to FORMAT_VERSION, using
+      the given version of the `rustdoc-types` crate"
+    );
+}
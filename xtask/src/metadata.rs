@@ -0,0 +1,106 @@
+//! Locating the source code of a package's dependencies with `cargo metadata`.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Context;
+use serde_json::Value;
+
+/// A dependency resolved by `cargo metadata`.
+pub struct Dependency {
+    /// The exact version that was resolved, such as `0.53.0`.
+    pub version: String,
+    /// The path to the root source file of the dependency, usually `src/lib.rs`.
+    pub lib_path: PathBuf,
+}
+
+/// Runs `cargo metadata` on the given manifest and returns its parsed output.
+///
+/// All features are enabled, so that optional dependencies are included in the dependency graph.
+pub fn cargo_metadata(manifest_path: &Path) -> anyhow::Result<Value> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+
+    let output = Command::new(cargo)
+        .arg("metadata")
+        .arg("--format-version=1")
+        .arg("--all-features")
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .output()
+        .context("failed to run `cargo metadata`")?;
+
+    anyhow::ensure!(
+        output.status.success(),
+        "`cargo metadata` failed:\n{}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    serde_json::from_slice(&output.stdout).context("failed to parse the output of `cargo metadata`")
+}
+
+/// Returns the dependency imported as `extern_name` by the workspace member `package_name`.
+///
+/// Renamed dependencies are supported, so `extern_name` may be `rustdoc_types_41` even though
+/// the package is named `rustdoc-types`.
+pub fn dependency(
+    metadata: &Value,
+    package_name: &str,
+    extern_name: &str,
+) -> anyhow::Result<Dependency> {
+    let packages = metadata["packages"]
+        .as_array()
+        .context("`cargo metadata` did not return a list of packages")?;
+
+    let workspace_members = metadata["workspace_members"]
+        .as_array()
+        .context("`cargo metadata` did not return a list of workspace members")?;
+
+    let package_id = workspace_members
+        .iter()
+        .find(|id| {
+            packages
+                .iter()
+                .any(|package| &package["id"] == *id && package["name"] == package_name)
+        })
+        .with_context(|| format!("could not find workspace member `{package_name}`"))?;
+
+    let node = metadata["resolve"]["nodes"]
+        .as_array()
+        .and_then(|nodes| nodes.iter().find(|node| &node["id"] == package_id))
+        .with_context(|| format!("could not find `{package_name}` in the dependency graph"))?;
+
+    let dependency_id = node["deps"]
+        .as_array()
+        .and_then(|deps| deps.iter().find(|dep| dep["name"] == extern_name))
+        .map(|dep| &dep["pkg"])
+        .with_context(|| format!("`{package_name}` does not depend on `{extern_name}`"))?;
+
+    let dependency = packages
+        .iter()
+        .find(|package| &package["id"] == dependency_id)
+        .with_context(|| format!("could not find the package for `{extern_name}`"))?;
+
+    let version = dependency["version"]
+        .as_str()
+        .with_context(|| format!("`{extern_name}` does not have a version"))?;
+
+    let lib_path = dependency["targets"]
+        .as_array()
+        .and_then(|targets| {
+            targets.iter().find(|target| {
+                target["kind"]
+                    .as_array()
+                    .is_some_and(|kinds| kinds.iter().any(|kind| kind == "lib"))
+            })
+        })
+        .and_then(|target| target["src_path"].as_str())
+        .with_context(|| format!("`{extern_name}` does not have a library target"))?;
+
+    Ok(Dependency {
+        version: version.to_owned(),
+        lib_path: PathBuf::from(lib_path),
+    })
+}
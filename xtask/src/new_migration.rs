@@ -0,0 +1,500 @@
+//! `cargo xtask new-migration`, which scaffolds the migration to a new format version.
+//!
+//! This task:
+//!
+//! 1. Resolves the new `rustdoc_types` and vendors its source in `macros/sources`, where it is read
+//!    by `impl_unchanged_migrations!`.
+//! 2. Adds the new `rustdoc_types` dependency, pinned to the vendored version, and its features to
+//!    `Cargo.toml`.
+//! 3. Compares the public types of the newest supported and the new `rustdoc_types`.
+//! 4. Generates `src/migrations/vXX.rs`, with stub `MigrateUp` implementations for every type
+//!    that changed. Unchanged types are handled by `impl_unchanged_migrations!`.
+//! 5. Registers the migration in `declare_migrations!`.
+//! 6. Creates a test in `tests/migrate.rs` and its source file in `tests/migrations`.
+//!
+//! Every file is generated before any of them are written, so a failed task leaves the repository
+//! unchanged.
+
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use anyhow::Context;
+
+use crate::{metadata, shape};
+
+/// A type that changed between two `rustdoc_types` versions, and why.
+struct Diverged {
+    name: String,
+    reason: String,
+}
+
+pub fn new_migration(
+    root: &Path,
+    to_version: u32,
+    rustdoc_types_version: &str,
+) -> anyhow::Result<()> {
+    let migrations_path = root.join("src/migrations/mod.rs");
+    let migrations = read(&migrations_path)?;
+
+    let current_version = newest_supported_version(&migrations)?;
+
+    anyhow::ensure!(
+        to_version > current_version,
+        "format version v{to_version} must be newer than the newest supported format version v{current_version}",
+    );
+
+    let sources_path = root.join("macros/sources");
+    let current_source = read(&sources_path.join(format!("rustdoc_types_{current_version}.rs")))?;
+
+    let up = resolve_rustdoc_types(to_version, rustdoc_types_version)?;
+    let up_source = read(&up.lib_path)?;
+
+    let vendored_path = sources_path.join(format!("rustdoc_types_{to_version}.rs"));
+    let vendored = format!(
+        "\
+// Vendored from `rustdoc-types {version}`, whose source is compared by `impl_unchanged_migrations!`.
+// Do not edit, run `cargo xtask new-migration` to vendor new versions.
+
+{up_source}",
+        version = up.version,
+    );
+
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = add_dependency(
+        &read(&manifest_path)?,
+        current_version,
+        to_version,
+        &up.version,
+    )?;
+
+    let diverged = diverged_types(current_version, &current_source, to_version, &up_source)?;
+
+    let module_path = root.join(format!("src/migrations/v{current_version}.rs"));
+    let module = migration_module(current_version, to_version, &diverged);
+
+    let migrations = register_migration(&migrations, current_version, to_version)?;
+
+    let tests_path = root.join("tests/migrate.rs");
+    let tests = add_test(&read(&tests_path)?, current_version, to_version);
+
+    let test_source_path = root.join(format!(
+        "tests/migrations/v{current_version}_to_v{to_version}.rs"
+    ));
+    let test_source =
+        format!("// TODO: Add items that exercise the changes made in v{to_version}.\n");
+
+    let new_files = [
+        (&vendored_path, &vendored),
+        (&module_path, &module),
+        (&test_source_path, &test_source),
+    ];
+
+    // Check that no new file would overwrite an existing one before writing anything.
+    for (path, _) in new_files {
+        anyhow::ensure!(!path.exists(), "`{}` already exists", path.display());
+    }
+
+    let changed_files = [
+        (&manifest_path, &manifest),
+        (&migrations_path, &migrations),
+        (&tests_path, &tests),
+    ];
+
+    for (path, contents) in new_files.into_iter().chain(changed_files) {
+        write(path, contents)?;
+    }
+
+    println!("Scaffolded the migration from v{current_version} to v{to_version}.");
+
+    if diverged.is_empty() {
+        println!("\nNo types changed, so no manual migrations are needed.");
+    } else {
+        println!("\nThe following types changed and need manual migrations:");
+
+        for Diverged { name, reason } in &diverged {
+            println!("  - `{name}`: {reason}");
+        }
+    }
+
+    println!(
+        "\nNext steps:
+  - Implement the `todo!()`s in `src/migrations/v{current_version}.rs` and describe the changes in its module documentation.
+  - Add items that exercise the changes to `tests/migrations/v{current_version}_to_v{to_version}.rs`, and queries to its test in `tests/migrate.rs`."
+    );
+
+    let toolchains = read(&root.join("tests/harness/toolchains.rs"))?;
+
+    if !toolchains.contains(&format!("({to_version}, \"nightly-")) {
+        println!(
+            "  - Add the nightly toolchain that generates v{to_version} to `tests/harness/toolchains.rs`."
+        );
+    }
+
+    Ok(())
+}
+
+/// Finds the newest format version in the `formats` of `declare_migrations!`.
+fn newest_supported_version(migrations: &str) -> anyhow::Result<u32> {
+    let mut lines = migrations.lines().map(str::trim);
+
+    // Skip the `macro_rules!` definition, which also contains `formats {`.
+    lines
+        .find(|line| *line == "declare_migrations! {")
+        .context("could not find the `declare_migrations!` invocation")?;
+
+    lines
+        .find(|line| *line == "formats {")
+        .context("could not find `formats` in `declare_migrations!`")?;
+
+    lines
+        .take_while(|line| *line != "}")
+        .filter_map(|line| line.split_once(" => "))
+        .filter_map(|(version, _)| version.parse().ok())
+        .last()
+        .context("could not find any format versions in `declare_migrations!`")
+}
+
+/// Returns `Cargo.toml` with `rustdoc_types_{to_version}`, pinned to the exact
+/// `rustdoc_types_version`, and its features added.
+fn add_dependency(
+    manifest: &str,
+    current_version: u32,
+    to_version: u32,
+    rustdoc_types_version: &str,
+) -> anyhow::Result<String> {
+    anyhow::ensure!(
+        !manifest.contains(&format!("rustdoc_types_{to_version} =")),
+        "`rustdoc_types_{to_version}` is already a dependency",
+    );
+
+    // Each line is inserted after the line for the current format version, or replaces it if
+    // `replace` is true.
+    let insertions = [
+        (
+            format!("v{current_version} = "),
+            false,
+            format!("v{to_version} = [\"dep:rustdoc_types_{to_version}\"]"),
+        ),
+        (
+            format!("min-v{current_version} = "),
+            true,
+            format!(
+                "min-v{current_version} = [\"v{current_version}\", \"min-v{to_version}\"]\nmin-v{to_version} = [\"v{to_version}\"]"
+            ),
+        ),
+        (
+            format!("rustdoc_types_{current_version} = "),
+            false,
+            format!(
+                "rustdoc_types_{to_version} = {{ version = \"={rustdoc_types_version}\", package = \"rustdoc-types\", features = [\"rustc-hash\"], optional = true }}"
+            ),
+        ),
+    ];
+
+    let mut new_manifest = String::with_capacity(manifest.len());
+    let mut inserted = [false; 3];
+
+    for line in manifest.lines() {
+        let insertion = insertions
+            .iter()
+            .position(|(prefix, _, _)| line.starts_with(prefix.as_str()));
+
+        match insertion {
+            Some(i) => {
+                let (_, replace, new_line) = &insertions[i];
+
+                if !replace {
+                    writeln!(new_manifest, "{line}").unwrap();
+                }
+
+                writeln!(new_manifest, "{new_line}").unwrap();
+
+                inserted[i] = true;
+            }
+            None => writeln!(new_manifest, "{line}").unwrap(),
+        }
+    }
+
+    for ((prefix, _, _), inserted) in insertions.iter().zip(inserted) {
+        anyhow::ensure!(
+            inserted,
+            "could not find `{}` in `Cargo.toml`",
+            prefix.trim_end_matches(" = "),
+        );
+    }
+
+    Ok(new_manifest)
+}
+
+/// Resolves the newest `rustdoc_types` matching the version requirement `rustdoc_types_version`.
+///
+/// `Cargo.toml` is not written until every file is generated, so the dependency is resolved in a
+/// temporary package instead.
+fn resolve_rustdoc_types(
+    to_version: u32,
+    rustdoc_types_version: &str,
+) -> anyhow::Result<metadata::Dependency> {
+    let package = TemporaryPackage::new(&format!(
+        "rustdoc_types_{to_version} = {{ version = \"{rustdoc_types_version}\", package = \"rustdoc-types\" }}"
+    ))?;
+
+    let metadata = metadata::cargo_metadata(&package.path.join("Cargo.toml"))?;
+
+    metadata::dependency(
+        &metadata,
+        TemporaryPackage::NAME,
+        &format!("rustdoc_types_{to_version}"),
+    )
+}
+
+/// Compares the shapes of the public types in both `rustdoc_types` sources, returning the types
+/// that cannot be migrated structurally.
+fn diverged_types(
+    current_version: u32,
+    current_source: &str,
+    to_version: u32,
+    up_source: &str,
+) -> anyhow::Result<Vec<Diverged>> {
+    let load_shapes = |version: u32, source: &str| {
+        shape::parse_shapes(source)
+            .with_context(|| format!("failed to parse the source of `rustdoc_types_{version}`"))
+    };
+
+    let current_shapes = load_shapes(current_version, current_source)?;
+    let up_shapes = load_shapes(to_version, up_source)?;
+
+    let diverged = current_shapes
+        .iter()
+        .filter_map(|(name, current_shape)| {
+            let reason = match up_shapes.get(name) {
+                Some(up_shape) => shape::compare(current_shape, up_shape).err()?,
+                None => "it was removed".to_owned(),
+            };
+
+            Some(Diverged {
+                name: name.clone(),
+                reason,
+            })
+        })
+        .collect();
+
+    Ok(diverged)
+}
+
+/// A temporary package with a single dependency, so that it can be resolved with
+/// `cargo metadata` without changing the workspace.
+///
+/// The package is deleted when this is dropped.
+struct TemporaryPackage {
+    path: PathBuf,
+}
+
+impl TemporaryPackage {
+    const NAME: &str = "new-migration-resolve";
+
+    fn new(dependency: &str) -> anyhow::Result<Self> {
+        let package = Self {
+            path: std::env::temp_dir().join(format!("{}-{}", Self::NAME, process::id())),
+        };
+
+        fs::create_dir_all(package.path.join("src"))
+            .with_context(|| format!("could not create `{}`", package.path.display()))?;
+
+        write(
+            &package.path.join("Cargo.toml"),
+            &format!(
+                "[package]
+name = \"{name}\"
+version = \"0.0.0\"
+edition = \"2024\"
+
+[dependencies]
+{dependency}
+
+# Do not become part of a workspace the temporary directory may be in.
+[workspace]
+",
+                name = Self::NAME,
+            ),
+        )?;
+
+        write(&package.path.join("src/lib.rs"), "")?;
+
+        Ok(package)
+    }
+}
+
+impl Drop for TemporaryPackage {
+    fn drop(&mut self) {
+        // Failing to clean up a temporary directory is not worth failing the task over.
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Generates the source of `src/migrations/v{current_version}.rs`.
+fn migration_module(current_version: u32, to_version: u32, diverged: &[Diverged]) -> String {
+    let mut module = format!(
+        "\
+//! **v{current_version} to v{to_version} migration.**
+//!
+//! TODO: Describe the changes made in this format version.
+//!
+//! <https://github.com/rust-lang/rustdoc-types/blob/trunk/CHANGELOG.md>
+
+use migrate_rustdoc_json_macros::impl_unchanged_migrations;
+use rustdoc_types_{current_version} as current;
+"
+    );
+
+    if diverged.is_empty() {
+        module.push_str(
+            "
+use crate::declare_migrate_up;
+",
+        );
+    } else {
+        writeln!(module, "use rustdoc_types_{to_version} as up;").unwrap();
+
+        module.push_str(
+            "
+use crate::{declare_migrate_up, reporter::Reporter, traits::MigrateUp};
+",
+        );
+    }
+
+    writeln!(
+        module,
+        "
+declare_migrate_up!({current_version}, {to_version});"
+    )
+    .unwrap();
+
+    for Diverged { name, reason } in diverged {
+        // Every `Item` advances the progress of the migration.
+        let migrated_item = if name == "Item" {
+            "\n        reporter.migrated_item();"
+        } else {
+            ""
+        };
+
+        writeln!(
+            module,
+            "
+/// TODO: Describe this migration. `{name}` changed because {reason}.
+impl MigrateUp for current::{name} {{
+    type Up = up::{name};
+
+    fn migrate_up(self, reporter: &mut Reporter) -> Self::Up {{
+        reporter.ran_override::<Self>();{migrated_item}
+
+        todo!(\"migrate `{name}` from v{current_version} to v{to_version}\")
+    }}
+}}"
+        )
+        .unwrap();
+    }
+
+    let manual: Vec<&str> = diverged
+        .iter()
+        .map(|diverged| diverged.name.as_str())
+        .collect();
+
+    writeln!(
+        module,
+        "
+impl_unchanged_migrations! {{
+    current: rustdoc_types_{current_version},
+    up: rustdoc_types_{to_version},
+    manual: [{manual}],
+}}",
+        manual = manual.join(", "),
+    )
+    .unwrap();
+
+    module
+}
+
+/// Returns `src/migrations/mod.rs` with the new format version and its migration registered in
+/// `declare_migrations!`.
+fn register_migration(
+    migrations: &str,
+    current_version: u32,
+    to_version: u32,
+) -> anyhow::Result<String> {
+    let current_format = format!("{current_version} => V{current_version}, ");
+
+    let lines: Vec<&str> = migrations.lines().collect();
+
+    let format_index = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with(&current_format))
+        .with_context(|| {
+            format!("could not find format version v{current_version} in `declare_migrations!`")
+        })?;
+
+    let migration_index = lines
+        .iter()
+        .rposition(|line| line.trim_start().starts_with("mod v"))
+        .context("could not find any migrations in `declare_migrations!`")?;
+
+    let mut new_migrations = String::with_capacity(migrations.len());
+
+    for (i, line) in lines.into_iter().enumerate() {
+        writeln!(new_migrations, "{line}").unwrap();
+
+        if i == format_index {
+            for skipped_version in (current_version + 1)..to_version {
+                writeln!(
+                    new_migrations,
+                    "        // v{skipped_version} does not exist."
+                )
+                .unwrap();
+            }
+
+            writeln!(
+                new_migrations,
+                "        {to_version} => V{to_version}, \"v{to_version}\", rustdoc_types_{to_version};"
+            )
+            .unwrap();
+        } else if i == migration_index {
+            writeln!(
+                new_migrations,
+                "        mod v{current_version} (V{current_version} => V{to_version}, \"v{current_version}\", \"v{to_version}\");"
+            )
+            .unwrap();
+        }
+    }
+
+    Ok(new_migrations)
+}
+
+/// Returns `tests/migrate.rs` with a test for the new migration.
+fn add_test(tests: &str, current_version: u32, to_version: u32) -> String {
+    let name = format!("v{current_version}_to_v{to_version}");
+    let mut tests = tests.to_owned();
+
+    write!(
+        tests,
+        "
+#[test]
+fn {name}() {{
+    MigrationTest::new({current_version}, {to_version}).test();
+}}
+"
+    )
+    .unwrap();
+
+    tests
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path).with_context(|| format!("could not read `{}`", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> anyhow::Result<()> {
+    fs::write(path, contents).with_context(|| format!("could not write `{}`", path.display()))
+}